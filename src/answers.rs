@@ -0,0 +1,292 @@
+use std::{collections::BTreeMap, fmt::Write, fs, path::Path, str::FromStr};
+
+use anyhow::Context;
+use serde::Deserialize;
+
+use crate::runner::Answers;
+
+pub const DEFAULT_PATH: &str = "answers.toml";
+
+/// Recorded answer, either a number or text (e.g. the letters of Day 5 or the picture of Day 10).
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+#[serde(untagged)]
+enum Answer {
+    Number(u64),
+    Text(String),
+}
+
+impl From<Answer> for String {
+    fn from(answer: Answer) -> Self {
+        match answer {
+            Answer::Number(n) => n.to_string(),
+            Answer::Text(text) => text,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+struct RawExpected {
+    part1: Option<Answer>,
+    part2: Option<Answer>,
+}
+
+/// Known answers of a single day, parts without a recorded answer are `None`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Expected {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl From<RawExpected> for Expected {
+    fn from(raw: RawExpected) -> Self {
+        Self {
+            part1: raw.part1.map(String::from),
+            part2: raw.part2.map(String::from),
+        }
+    }
+}
+
+impl FromStr for Expected {
+    type Err = anyhow::Error;
+
+    /// Parses a single day's table without the `[dayNN]` header, like `part1 = 24000`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(toml::from_str::<RawExpected>(s)?.into())
+    }
+}
+
+/// Known answers for the real inputs, keyed by day number.
+///
+/// Stored as TOML with one table per day:
+///
+/// ```toml
+/// [day01]
+/// part1 = 24000
+/// part2 = 45000
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AnswerRegistry(BTreeMap<usize, Expected>);
+
+impl FromStr for AnswerRegistry {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let raw: BTreeMap<String, RawExpected> = toml::from_str(s)?;
+        raw.into_iter()
+            .map(|(key, expected)| {
+                let day = key
+                    .strip_prefix("day")
+                    .and_then(|day| day.parse().ok())
+                    .ok_or_else(|| anyhow::anyhow!("Invalid day key: {:?}", key))?;
+                Ok((day, expected.into()))
+            })
+            .collect::<anyhow::Result<_>>()
+            .map(Self)
+    }
+}
+
+impl AnswerRegistry {
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        fs::read_to_string(path)
+            .with_context(|| format!("Can't read answers from {}", path.display()))?
+            .parse()
+            .with_context(|| format!("Can't parse answers from {}", path.display()))
+    }
+
+    pub fn get(&self, day: usize) -> Option<&Expected> {
+        self.0.get(&day)
+    }
+
+    pub fn check(&self, day: usize, answers: &Answers) -> [Status; 2] {
+        self.get(day).cloned().unwrap_or_default().check(answers)
+    }
+}
+
+impl Expected {
+    pub fn check(self, answers: &Answers) -> [Status; 2] {
+        [
+            Status::new(self.part1, &answers.part1.answer),
+            Status::new(self.part2, &answers.part2.answer),
+        ]
+    }
+}
+
+/// Result of comparing a single part against its recorded answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail { expected: String, actual: String },
+    Missing,
+}
+
+impl Status {
+    fn new(expected: Option<String>, actual: &str) -> Self {
+        match expected {
+            None => Self::Missing,
+            Some(expected) if expected == actual => Self::Pass,
+            Some(expected) => Self::Fail {
+                expected,
+                actual: actual.to_owned(),
+            },
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Status::Pass => "pass",
+            Status::Fail { .. } => "FAIL",
+            Status::Missing => "missing",
+        }
+    }
+}
+
+/// Outcome of verifying a single day, days that failed to run have an error instead of statuses.
+pub type Verification = (usize, anyhow::Result<[Status; 2]>);
+
+pub fn render_report(results: &[Verification]) -> String {
+    let mut report = String::from("Day | Part 1  | Part 2\n--- | ------- | -------\n");
+    let mut details = String::new();
+    for (day, result) in results {
+        match result {
+            Ok(statuses) => {
+                writeln!(
+                    report,
+                    "{:>3} | {:<7} | {}",
+                    day,
+                    statuses[0].label(),
+                    statuses[1].label()
+                )
+                .unwrap();
+                for (part, status) in statuses.iter().enumerate() {
+                    if let Status::Fail { expected, actual } = status {
+                        writeln!(
+                            details,
+                            "Day {} part {}: expected {:?}, got {:?}",
+                            day,
+                            part + 1,
+                            expected,
+                            actual
+                        )
+                        .unwrap();
+                    }
+                }
+            }
+            Err(e) => {
+                writeln!(report, "{:>3} | ERROR   | ERROR", day).unwrap();
+                writeln!(details, "Day {}: {:#}", day, e).unwrap();
+            }
+        }
+    }
+    if !details.is_empty() {
+        report.push('\n');
+        report.push_str(&details);
+    }
+    report
+}
+
+/// Number of days that failed to run plus the number of parts with a wrong answer.
+pub fn count_failures(results: &[Verification]) -> usize {
+    results
+        .iter()
+        .map(|(_, result)| match result {
+            Ok(statuses) => statuses
+                .iter()
+                .filter(|status| matches!(status, Status::Fail { .. }))
+                .count(),
+            Err(_) => 1,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        days,
+        input::Source,
+        runner::{AnswerType, PartAnswer},
+    };
+
+    #[test]
+    fn test_parsing() {
+        let registry: AnswerRegistry = concat!(
+            "[day01]\n",
+            "part1 = 24000\n",
+            "part2 = \"45000\"\n",
+            "\n",
+            "[day10]\n",
+            "part1 = 13140\n",
+        )
+        .parse()
+        .unwrap();
+        assert_eq!(
+            registry.get(1),
+            Some(&Expected {
+                part1: Some("24000".to_owned()),
+                part2: Some("45000".to_owned()),
+            })
+        );
+        assert_eq!(
+            registry.get(10),
+            Some(&Expected {
+                part1: Some("13140".to_owned()),
+                part2: None,
+            })
+        );
+        assert_eq!(registry.get(2), None);
+
+        assert!("[first]\npart1 = 1".parse::<AnswerRegistry>().is_err());
+        assert!("[day01]\npart3 = 1".parse::<AnswerRegistry>().is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let registry: AnswerRegistry = "[day01]\npart1 = 24000".parse().unwrap();
+        let answers = |part1: &str| {
+            let part = |answer: &str| PartAnswer {
+                answer: answer.to_owned(),
+                answer_type: AnswerType::Integer,
+                duration: Default::default(),
+            };
+            Answers {
+                part1: part(part1),
+                part2: part("45000"),
+            }
+        };
+        assert_eq!(
+            registry.check(1, &answers("24000")),
+            [Status::Pass, Status::Missing]
+        );
+        assert_eq!(
+            registry.check(1, &answers("1")),
+            [
+                Status::Fail {
+                    expected: "24000".to_owned(),
+                    actual: "1".to_owned()
+                },
+                Status::Missing
+            ]
+        );
+    }
+
+    #[test]
+    fn test_recorded_answers() {
+        let registry = AnswerRegistry::load(DEFAULT_PATH).unwrap();
+        for day in days::DAYS {
+            let answers = day
+                .solve(&Source::Default.read(day.number).unwrap())
+                .unwrap();
+            for (part, status) in registry.check(day.number, &answers).iter().enumerate() {
+                assert!(
+                    !matches!(status, Status::Fail { .. }),
+                    "Day {} part {}: {:?}",
+                    day.number,
+                    part + 1,
+                    status
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use std::{
+    fmt::Write,
+    hint::black_box,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use aoc_helpers::scaffold::{Parse, Problem};
+
+/// Minimum and median of the durations collected over all repetitions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+}
+
+impl Stats {
+    fn from_samples(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty(), "There should be at least one sample");
+        samples.sort_unstable();
+        Self {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+        }
+    }
+}
+
+/// Timings of a single day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Timings {
+    pub repetitions: usize,
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+}
+
+fn time<T>(repetitions: usize, mut fun: impl FnMut() -> T) -> Stats {
+    Stats::from_samples(
+        (0..repetitions)
+            .map(|_| {
+                let start = Instant::now();
+                black_box(fun());
+                start.elapsed()
+            })
+            .collect(),
+    )
+}
+
+/// Times parsing and both parts separately; the parts are solved on a single parsed input.
+pub fn measure<P: Problem>(raw_input: &str, repetitions: usize) -> anyhow::Result<Timings> {
+    assert!(repetitions > 0, "There should be at least one repetition");
+    let input = P::Input::parse(raw_input)?;
+    Ok(Timings {
+        repetitions,
+        parse: time(repetitions, || P::Input::parse(black_box(raw_input))),
+        part1: time(repetitions, || P::solve_part1(black_box(&input))),
+        part2: time(repetitions, || P::solve_part2(black_box(&input))),
+    })
+}
+
+/// Output format of the benchmark report.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ReportFormat {
+    #[default]
+    Markdown,
+    Csv,
+}
+
+impl FromStr for ReportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "markdown" | "md" => Ok(Self::Markdown),
+            "csv" => Ok(Self::Csv),
+            _ => Err(anyhow::anyhow!("Unknown report format: {:?}", s)),
+        }
+    }
+}
+
+pub fn render_report(results: &[(usize, Timings)], format: ReportFormat) -> String {
+    let mut report = String::new();
+    match format {
+        ReportFormat::Markdown => {
+            report.push_str(concat!(
+                "| Day | Repetitions | Parse min | Parse median ",
+                "| Part 1 min | Part 1 median | Part 2 min | Part 2 median |\n",
+                "|----:|------------:|----------:|-------------:",
+                "|-----------:|--------------:|-----------:|--------------:|\n",
+            ));
+            for (day, timings) in results {
+                writeln!(
+                    report,
+                    "| {} | {} | {:.2?} | {:.2?} | {:.2?} | {:.2?} | {:.2?} | {:.2?} |",
+                    day,
+                    timings.repetitions,
+                    timings.parse.min,
+                    timings.parse.median,
+                    timings.part1.min,
+                    timings.part1.median,
+                    timings.part2.min,
+                    timings.part2.median,
+                )
+                .unwrap();
+            }
+        }
+        ReportFormat::Csv => {
+            report.push_str(concat!(
+                "day,repetitions,parse_min_ns,parse_median_ns,",
+                "part1_min_ns,part1_median_ns,part2_min_ns,part2_median_ns\n",
+            ));
+            for (day, timings) in results {
+                writeln!(
+                    report,
+                    "{},{},{},{},{},{},{},{}",
+                    day,
+                    timings.repetitions,
+                    timings.parse.min.as_nanos(),
+                    timings.parse.median.as_nanos(),
+                    timings.part1.min.as_nanos(),
+                    timings.part1.median.as_nanos(),
+                    timings.part2.min.as_nanos(),
+                    timings.part2.median.as_nanos(),
+                )
+                .unwrap();
+            }
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::from_samples(
+            [5, 1, 4, 2, 3]
+                .into_iter()
+                .map(Duration::from_nanos)
+                .collect(),
+        );
+        assert_eq!(stats.min, Duration::from_nanos(1));
+        assert_eq!(stats.median, Duration::from_nanos(3));
+    }
+
+    #[test]
+    fn test_csv_report() {
+        let stats = |ns| Stats {
+            min: Duration::from_nanos(ns),
+            median: Duration::from_nanos(ns + 1),
+        };
+        let timings = Timings {
+            repetitions: 3,
+            parse: stats(10),
+            part1: stats(20),
+            part2: stats(30),
+        };
+        assert_eq!(
+            render_report(&[(7, timings)], ReportFormat::Csv),
+            concat!(
+                "day,repetitions,parse_min_ns,parse_median_ns,",
+                "part1_min_ns,part1_median_ns,part2_min_ns,part2_median_ns\n",
+                "7,3,10,11,20,21,30,31\n",
+            )
+        );
+    }
+}
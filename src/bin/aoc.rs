@@ -0,0 +1,5 @@
+use advent_of_code_2022::cli::Command;
+
+fn main() -> anyhow::Result<()> {
+    Command::from_args(std::env::args().skip(1))?.execute()
+}
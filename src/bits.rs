@@ -0,0 +1,106 @@
+/// Fixed-size set of small numbers.
+#[derive(Clone)]
+pub struct Bits(Vec<u64>);
+
+impl Bits {
+    pub fn new(len: usize) -> Self {
+        Self(vec![0; len.div_ceil(64)])
+    }
+
+    pub fn get(&self, idx: usize) -> bool {
+        self.0
+            .get(idx / 64)
+            .is_some_and(|word| word & (1 << (idx % 64)) != 0)
+    }
+
+    pub fn set(&mut self, idx: usize) {
+        self.0[idx / 64] |= 1 << (idx % 64);
+    }
+
+    /// Adds every number of `other` increased by `shift`, dropping the ones that don't fit.
+    pub fn or_shifted(&mut self, other: &Self, shift: usize) {
+        let (words, bits) = (shift / 64, shift % 64);
+        for idx in (words..self.0.len()).rev() {
+            let low = other.0[idx - words] << bits;
+            let high = match (bits, idx.checked_sub(words + 1)) {
+                (0, _) | (_, None) => 0,
+                (_, Some(from)) => other.0[from] >> (64 - bits),
+            };
+            self.0[idx] |= low | high;
+        }
+    }
+
+    /// The smallest number of this set whose difference to `total` is in `other`.
+    pub fn first_sum(&self, other: &Self, total: usize) -> Option<usize> {
+        // bit `n` of `other` reversed is bit `total - n`
+        let words = (total + 1).div_ceil(64);
+        let shift = words * 64 - (total + 1);
+        let reversed_word = |idx: usize| {
+            other
+                .0
+                .get(words - 1 - idx)
+                .map_or(0, |word| word.reverse_bits())
+        };
+        (0..words).find_map(|idx| {
+            let high = match (shift, idx + 1 < words) {
+                (0, _) | (_, false) => 0,
+                _ => reversed_word(idx + 1) << (64 - shift),
+            };
+            let both = self.0.get(idx).copied().unwrap_or(0) & (reversed_word(idx) >> shift | high);
+            (both != 0).then(|| idx * 64 + both.trailing_zeros() as usize)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn test_bits() {
+        let mut bits = Bits::new(200);
+        bits.set(3);
+        bits.set(70);
+        let mut shifted = Bits::new(200);
+        shifted.or_shifted(&bits, 100);
+        let set: Vec<usize> = (0..200).filter(|idx| shifted.get(*idx)).collect();
+        assert_eq!(set, vec![103, 170]);
+        shifted.or_shifted(&bits, 0);
+        assert_eq!(
+            (0..200).filter(|idx| shifted.get(*idx)).collect::<Vec<_>>(),
+            vec![3, 70, 103, 170]
+        );
+    }
+
+    #[test]
+    fn test_first_sum() {
+        let mut bits = Bits::new(200);
+        bits.set(3);
+        bits.set(70);
+        bits.set(150);
+        let mut other = Bits::new(200);
+        other.set(0);
+        other.set(100);
+        assert_eq!(bits.first_sum(&other, 170), Some(70));
+        assert_eq!(bits.first_sum(&other, 150), Some(150));
+        assert_eq!(bits.first_sum(&other, 103), Some(3));
+        assert_eq!(bits.first_sum(&other, 104), None);
+        assert_eq!(bits.first_sum(&other, 63), None);
+        assert_eq!(Bits::new(1).first_sum(&Bits::new(1), 0), None);
+
+        // against checking every split
+        let mut rng = rand::rngs::StdRng::seed_from_u64(13);
+        for _ in 0..100 {
+            let len = rng.gen_range(1..300);
+            let (mut a, mut b) = (Bits::new(len), Bits::new(len));
+            for _ in 0..rng.gen_range(0..10) {
+                a.set(rng.gen_range(0..len));
+                b.set(rng.gen_range(0..len));
+            }
+            let total = rng.gen_range(0..len);
+            let expected = (0..=total).find(|n| a.get(*n) && b.get(total - n));
+            assert_eq!(a.first_sum(&b, total), expected);
+        }
+    }
+}
@@ -0,0 +1,379 @@
+use std::path::{Path, PathBuf};
+
+use crate::{
+    answers::{self, AnswerRegistry},
+    bench::{self, ReportFormat},
+    input::Source,
+    runner::{self, Day, OutputFormat, Selection},
+    template,
+};
+
+pub const USAGE: &str = concat!(
+    "Usage:\n",
+    "  aoc run <DAYS> [--input <PATH>] [--format table|json]\n",
+    "                                     solve the selected days\n",
+    "  aoc bench <DAYS> [--input <PATH>] [--repetitions <N>] [--format markdown|csv]\n",
+    "                                     time parsing and both parts of the selected days\n",
+    "  aoc verify [<DAYS>] [--answers <PATH>]\n",
+    "                                     check the inputs in `inputs/` against known answers\n",
+    "  aoc new <DAY>                      generate the module, binary and input placeholder of a day\n",
+    "\n",
+    "<DAYS> is `all`, a single day (`7`) or an inclusive range (`3..9`)\n",
+    "<PATH> is a file or `-` for stdin, defaults to `inputs/dayNN.txt`",
+);
+
+/// Subcommand of the `aoc` runner.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Command {
+    Run {
+        selection: Selection,
+        input: Source,
+        format: OutputFormat,
+    },
+    Bench {
+        selection: Selection,
+        input: Source,
+        repetitions: usize,
+        format: ReportFormat,
+    },
+    Verify {
+        selection: Selection,
+        answers: PathBuf,
+    },
+    New {
+        day: usize,
+    },
+}
+
+const DEFAULT_REPETITIONS: usize = 10;
+
+/// Positional arguments and `--name value` options, in order of appearance.
+#[derive(Debug, Default)]
+struct Args<'a> {
+    positional: Vec<&'a str>,
+    options: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> Args<'a> {
+    fn parse(args: &[&'a str]) -> anyhow::Result<Self> {
+        let mut result = Self::default();
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            if let Some(name) = arg.strip_prefix("--") {
+                let (name, value) = match name.split_once('=') {
+                    Some((name, value)) => (name, value),
+                    None => (
+                        name,
+                        *iter
+                            .next()
+                            .ok_or_else(|| anyhow::anyhow!("Missing value for --{}", name))?,
+                    ),
+                };
+                result.options.push((name, value));
+            } else {
+                result.positional.push(arg);
+            }
+        }
+        Ok(result)
+    }
+
+    /// Removes the option with the given name and returns its value.
+    fn take_option(&mut self, name: &str) -> anyhow::Result<Option<&'a str>> {
+        let mut values = Vec::new();
+        self.options.retain(|(option, value)| {
+            let matches = *option == name;
+            if matches {
+                values.push(*value);
+            }
+            !matches
+        });
+        match values.as_slice() {
+            [] => Ok(None),
+            [value] => Ok(Some(value)),
+            _ => Err(anyhow::anyhow!("--{} given more than once", name)),
+        }
+    }
+
+    /// Fails if there are options left that no one asked for.
+    fn finish(&self) -> anyhow::Result<()> {
+        if let Some((name, _)) = self.options.first() {
+            Err(anyhow::anyhow!("Unknown option --{}", name))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl Command {
+    pub fn from_args<I, S>(args: I) -> anyhow::Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let args: Vec<S> = args.into_iter().collect();
+        let args: Vec<&str> = args.iter().map(AsRef::as_ref).collect();
+        Self::parse(&args).map_err(|e| anyhow::anyhow!("{}\n\n{}", e, USAGE))
+    }
+
+    fn parse(args: &[&str]) -> anyhow::Result<Self> {
+        let mut args = Args::parse(args)?;
+        let input = args
+            .take_option("input")?
+            .map(Source::from)
+            .unwrap_or_default();
+        let command = match args.positional.clone().as_slice() {
+            ["run", selection] => Self::Run {
+                selection: selection.parse()?,
+                input,
+                format: args
+                    .take_option("format")?
+                    .map(str::parse)
+                    .transpose()?
+                    .unwrap_or_default(),
+            },
+            ["bench", selection] => Self::Bench {
+                selection: selection.parse()?,
+                input,
+                repetitions: match args.take_option("repetitions")? {
+                    Some(n) => match n.parse() {
+                        Ok(0) | Err(_) => {
+                            return Err(anyhow::anyhow!("Invalid number of repetitions: {:?}", n))
+                        }
+                        Ok(n) => n,
+                    },
+                    None => DEFAULT_REPETITIONS,
+                },
+                format: args
+                    .take_option("format")?
+                    .map(str::parse)
+                    .transpose()?
+                    .unwrap_or_default(),
+            },
+            ["verify"] | ["verify", _] if input == Source::Default => Self::Verify {
+                selection: match args.positional.get(1) {
+                    Some(selection) => selection.parse()?,
+                    None => Selection::All,
+                },
+                answers: args
+                    .take_option("answers")?
+                    .unwrap_or(answers::DEFAULT_PATH)
+                    .into(),
+            },
+            ["new", day] if input == Source::Default => Self::New {
+                day: day
+                    .parse()
+                    .map_err(|e| anyhow::anyhow!("Invalid day {:?}: {}", day, e))?,
+            },
+            positional => return Err(anyhow::anyhow!("Invalid arguments: {:?}", positional)),
+        };
+        args.finish()?;
+        Ok(command)
+    }
+
+    pub fn execute(&self) -> anyhow::Result<()> {
+        match self {
+            Command::Run {
+                selection,
+                input,
+                format,
+            } => {
+                let results = selected_days(selection, input)?
+                    .into_iter()
+                    .map(|day| Ok((day.number, day.solve(&input.read(day.number)?)?)))
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                match format {
+                    OutputFormat::Table => print!("{}", runner::render_table(&results)),
+                    OutputFormat::Json => print!("{}", runner::render_json(&results)),
+                }
+                Ok(())
+            }
+            Command::Bench {
+                selection,
+                input,
+                repetitions,
+                format,
+            } => {
+                let results = selected_days(selection, input)?
+                    .into_iter()
+                    .map(|day| {
+                        let raw_input = input.read(day.number)?;
+                        Ok((day.number, day.bench(&raw_input, *repetitions)?))
+                    })
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                print!("{}", bench::render_report(&results, *format));
+                Ok(())
+            }
+            Command::Verify { selection, answers } => {
+                let registry = AnswerRegistry::load(answers)?;
+                let results: Vec<answers::Verification> = selection
+                    .days()?
+                    .into_iter()
+                    .map(|day| {
+                        let result = Source::Default
+                            .read(day.number)
+                            .and_then(|raw_input| day.solve(&raw_input))
+                            .map(|answers| registry.check(day.number, &answers));
+                        (day.number, result)
+                    })
+                    .collect();
+                print!("{}", answers::render_report(&results));
+                match answers::count_failures(&results) {
+                    0 => Ok(()),
+                    failures => Err(anyhow::anyhow!("{} verification failure(s)", failures)),
+                }
+            }
+            Command::New { day } => {
+                for path in template::generate(Path::new("."), *day)? {
+                    println!("Wrote {}", path.display());
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+fn selected_days(selection: &Selection, input: &Source) -> anyhow::Result<Vec<&'static Day>> {
+    let days = selection.days()?;
+    if days.len() > 1 && *input != Source::Default {
+        Err(anyhow::anyhow!(
+            "--input can only be used when running a single day"
+        ))
+    } else {
+        Ok(days)
+    }
+}
+
+/// Reads the input for a single day binary, which accepts only `--input <PATH>`.
+pub fn day_input<I, S>(day: usize, args: I) -> anyhow::Result<String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let args: Vec<S> = args.into_iter().collect();
+    let args: Vec<&str> = args.iter().map(AsRef::as_ref).collect();
+    let mut args = Args::parse(&args)?;
+    let input = args.take_option("input")?.map(Source::from);
+    args.finish()?;
+    if let Some(arg) = args.positional.first() {
+        return Err(anyhow::anyhow!(
+            "Unexpected argument {:?}, usage: day{:02} [--input <PATH>]",
+            arg,
+            day
+        ));
+    }
+    input.unwrap_or_default().read(day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parsing() {
+        assert_eq!(
+            Command::from_args(["run", "all"]).unwrap(),
+            Command::Run {
+                selection: Selection::All,
+                input: Source::Default,
+                format: OutputFormat::Table,
+            }
+        );
+        assert_eq!(
+            Command::from_args(["run", "3..9"]).unwrap(),
+            Command::Run {
+                selection: Selection::Range(3..=9),
+                input: Source::Default,
+                format: OutputFormat::Table,
+            }
+        );
+        assert!(Command::from_args(["run"]).is_err());
+        assert!(Command::from_args(["walk", "7"]).is_err());
+    }
+
+    #[test]
+    fn test_input_option() {
+        assert_eq!(
+            Command::from_args(["run", "7", "--input", "-"]).unwrap(),
+            Command::Run {
+                selection: Selection::Range(7..=7),
+                input: Source::Stdin,
+                format: OutputFormat::Table,
+            }
+        );
+        assert_eq!(
+            Command::from_args(["run", "--input=other.txt", "7"]).unwrap(),
+            Command::Run {
+                selection: Selection::Range(7..=7),
+                input: Source::Path("other.txt".into()),
+                format: OutputFormat::Table,
+            }
+        );
+        assert_eq!(
+            Command::from_args(["run", "all", "--format", "json"]).unwrap(),
+            Command::Run {
+                selection: Selection::All,
+                input: Source::Default,
+                format: OutputFormat::Json,
+            }
+        );
+        assert!(Command::from_args(["run", "all", "--format", "csv"]).is_err());
+        assert!(Command::from_args(["run", "7", "--input"]).is_err());
+        assert!(Command::from_args(["run", "7", "--input", "a", "--input", "b"]).is_err());
+        assert!(Command::from_args(["run", "7", "--output", "a"]).is_err());
+    }
+
+    #[test]
+    fn test_bench_parsing() {
+        assert_eq!(
+            Command::from_args(["bench", "all"]).unwrap(),
+            Command::Bench {
+                selection: Selection::All,
+                input: Source::Default,
+                repetitions: DEFAULT_REPETITIONS,
+                format: ReportFormat::Markdown,
+            }
+        );
+        assert_eq!(
+            Command::from_args(["bench", "1..5", "--repetitions", "3", "--format", "csv"]).unwrap(),
+            Command::Bench {
+                selection: Selection::Range(1..=5),
+                input: Source::Default,
+                repetitions: 3,
+                format: ReportFormat::Csv,
+            }
+        );
+        assert!(Command::from_args(["bench", "all", "--repetitions", "0"]).is_err());
+        assert!(Command::from_args(["bench", "all", "--format", "xml"]).is_err());
+        assert!(Command::from_args(["run", "all", "--repetitions", "3"]).is_err());
+    }
+
+    #[test]
+    fn test_verify_parsing() {
+        assert_eq!(
+            Command::from_args(["verify"]).unwrap(),
+            Command::Verify {
+                selection: Selection::All,
+                answers: answers::DEFAULT_PATH.into(),
+            }
+        );
+        assert_eq!(
+            Command::from_args(["verify", "7", "--answers", "other.toml"]).unwrap(),
+            Command::Verify {
+                selection: Selection::Range(7..=7),
+                answers: "other.toml".into(),
+            }
+        );
+        assert!(Command::from_args(["verify", "--input", "day07.txt"]).is_err());
+    }
+
+    #[test]
+    fn test_new_parsing() {
+        assert_eq!(
+            Command::from_args(["new", "14"]).unwrap(),
+            Command::New { day: 14 }
+        );
+        assert!(Command::from_args(["new"]).is_err());
+        assert!(Command::from_args(["new", "x"]).is_err());
+        assert!(Command::from_args(["new", "14", "--input", "a"]).is_err());
+    }
+}
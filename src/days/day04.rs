@@ -0,0 +1,73 @@
+use aoc_helpers::prelude::*;
+use rematch::rematch;
+
+pub struct Day04;
+
+#[derive(Clone, Copy, Debug)]
+#[rematch(r"([0-9]+)-([0-9]+)")]
+pub struct Range {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Range {
+    pub fn contains_fully(&self, other: &Range) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    pub fn contains(&self, point: usize) -> bool {
+        self.start <= point && point <= self.end
+    }
+
+    pub fn overlaps(&self, other: &Range) -> bool {
+        self.contains(other.start)
+            || self.contains(other.end)
+            || other.contains(self.start)
+            || other.contains(self.end)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[rematch(r"([0-9]+-[0-9]+),([0-9]+-[0-9]+)")]
+pub struct RangePair {
+    pub first: Range,
+    pub second: Range,
+}
+
+impl RangePair {
+    pub fn contains_fully(&self) -> bool {
+        self.first.contains_fully(&self.second) || self.second.contains_fully(&self.first)
+    }
+
+    pub fn has_overlap(&self) -> bool {
+        self.first.overlaps(&self.second)
+    }
+}
+
+impl Problem for Day04 {
+    type Input = VecFromLines<RangePair>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn solve_part1(input: &<Self::Input as aoc_helpers::scaffold::Parse>::Parsed) -> Self::Part1 {
+        input.iter().filter(|pair| pair.contains_fully()).count()
+    }
+
+    fn solve_part2(input: &<Self::Input as aoc_helpers::scaffold::Parse>::Parsed) -> Self::Part2 {
+        input.iter().filter(|pair| pair.has_overlap()).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_helpers::scaffold::{solve_part1, solve_part2};
+
+    const SAMPLE: &str = include_str!("../../inputs/samples/day04-sample.txt");
+
+    #[test]
+    fn test_sample() {
+        assert_eq!(solve_part1::<Day04>(SAMPLE), 2);
+        assert_eq!(solve_part2::<Day04>(SAMPLE), 4);
+    }
+}
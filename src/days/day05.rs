@@ -0,0 +1,188 @@
+use aoc_helpers::prelude::*;
+use rematch::rematch;
+
+use crate::parse_error::{lines_with_offsets, Lines, ParseError, Sections};
+
+pub struct Day05;
+
+#[derive(Clone, Debug)]
+pub struct Stacks(pub Vec<Vec<char>>);
+
+impl aoc_helpers::scaffold::Parse for Stacks {
+    type Parsed = Self;
+    fn parse(raw_input: &str) -> anyhow::Result<Self::Parsed> {
+        lazy_static::lazy_static! {
+            static ref NUMBER: regex::Regex = regex::Regex::new(r"\S+").unwrap();
+        }
+
+        let error = |offset, message: String| ParseError::new(5, raw_input, offset, message);
+        let mut lines = lines_with_offsets(raw_input).collect::<Vec<_>>();
+
+        // the last line numbers the stacks: " 1   2   3 "
+        let (numbers_offset, numbers) = lines
+            .pop()
+            .ok_or_else(|| error(0, "Expected stacks".to_owned()))?;
+        let mut count = 0;
+        for number in NUMBER.find_iter(numbers) {
+            if number.start() != 4 * count + 1 || number.as_str().parse() != Ok(count + 1) {
+                return Err(error(
+                    numbers_offset + number.start(),
+                    format!(
+                        "Expected stack number {} at column {}",
+                        count + 1,
+                        4 * count + 2
+                    ),
+                )
+                .into());
+            }
+            count += 1;
+        }
+        if count == 0 {
+            return Err(error(numbers_offset, "Expected stack numbers".to_owned()).into());
+        }
+
+        // every other line has a crate (`[A]`) or 3 spaces for each stack, separated by a space
+        let mut result = vec![Vec::new(); count];
+        for (offset, line) in lines.into_iter().rev() {
+            for (idx, stack) in result.iter_mut().enumerate() {
+                let cell_offset = 4 * idx;
+                match line.get(cell_offset..(cell_offset + 3).min(line.len())) {
+                    None | Some("") | Some("   ") => {}
+                    Some(cell) => match cell.as_bytes() {
+                        [b'[', c, b']'] if c.is_ascii_alphabetic() => stack.push(*c as char),
+                        _ => {
+                            return Err(error(
+                                offset + cell_offset,
+                                "Expected a crate like `[A]` or 3 spaces".to_owned(),
+                            )
+                            .into())
+                        }
+                    },
+                }
+            }
+            if let Some(rest) = line.get(4 * count - 1..) {
+                if let Some(idx) = rest.find(|c| c != ' ') {
+                    return Err(error(
+                        offset + 4 * count - 1 + idx,
+                        "Unexpected crate outside of numbered stacks".to_owned(),
+                    )
+                    .into());
+                }
+            }
+        }
+
+        Ok(Self(result))
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[rematch(r"move (\d+) from (\d+) to (\d+)")]
+pub struct Command {
+    pub quantity: usize,
+    pub source: usize,
+    pub target: usize,
+}
+
+impl Problem for Day05 {
+    type Input = Sections<Stacks, Lines<Command>>;
+    type Part1 = String;
+    type Part2 = String;
+
+    fn solve_part1(input: &<Self::Input as aoc_helpers::scaffold::Parse>::Parsed) -> Self::Part1 {
+        let mut stacks = input.0 .0.clone();
+        for Command {
+            quantity,
+            source,
+            target,
+        } in &input.1
+        {
+            for _ in 0..*quantity {
+                if let Some(c) = stacks[*source - 1].pop() {
+                    stacks[*target - 1].push(c);
+                }
+            }
+        }
+        stacks
+            .into_iter()
+            .map(|mut s| s.pop().unwrap_or(' '))
+            .collect()
+    }
+
+    fn solve_part2(input: &<Self::Input as aoc_helpers::scaffold::Parse>::Parsed) -> Self::Part2 {
+        let mut stacks = input.0 .0.clone();
+        for Command {
+            quantity,
+            source,
+            target,
+        } in &input.1
+        {
+            if source == target {
+                continue;
+            }
+
+            let mut target_vec = Vec::new();
+            let mut source_vec = Vec::new();
+
+            std::mem::swap(&mut target_vec, &mut stacks[*target - 1]);
+            std::mem::swap(&mut source_vec, &mut stacks[*source - 1]);
+
+            target_vec.extend(source_vec.drain((source_vec.len() - *quantity)..));
+
+            std::mem::swap(&mut target_vec, &mut stacks[*target - 1]);
+            std::mem::swap(&mut source_vec, &mut stacks[*source - 1]);
+        }
+        stacks
+            .into_iter()
+            .map(|mut s| s.pop().unwrap_or(' '))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_helpers::scaffold::{solve_part1, solve_part2, Parse};
+
+    const SAMPLE: &str = include_str!("../../inputs/samples/day05-sample.txt");
+
+    #[test]
+    fn test_stacks_parsing() {
+        let stacks_str = concat!(
+            "    [D]    \n",
+            "[N] [C]    \n",
+            "[Z] [M] [P]\n",
+            " 1   2   3 "
+        );
+        let stacks = Stacks::parse(stacks_str).unwrap();
+        assert_eq!(
+            stacks.0,
+            vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']]
+        );
+    }
+
+    #[test]
+    fn test_stacks_parsing_errors() {
+        let error = |stacks_str: &str| {
+            Stacks::parse(stacks_str)
+                .unwrap_err()
+                .downcast::<ParseError>()
+                .unwrap()
+        };
+
+        let err = error(concat!("    [D]    \n", "[N] (C)    \n", " 1   2   3 "));
+        assert_eq!((err.line, err.column), (2, 5));
+        assert_eq!(err.snippet, "[N] (C)    ");
+
+        let err = error(concat!("    [D]    \n", "[N] [C]    \n", " 1   3   2 "));
+        assert_eq!((err.line, err.column), (3, 6));
+
+        let err = error(concat!("    [D]     [E]\n", "[N] [C]    \n", " 1   2   3 "));
+        assert_eq!((err.line, err.column), (1, 13));
+    }
+
+    #[test]
+    fn test_sample() {
+        assert_eq!(solve_part1::<Day05>(SAMPLE), "CMZ".to_owned());
+        assert_eq!(solve_part2::<Day05>(SAMPLE), "MCD".to_owned());
+    }
+}
@@ -0,0 +1,30 @@
+use aoc_helpers::prelude::*;
+
+pub struct Day06;
+
+pub fn find_first_unique(bytes: &[u8], size: usize) -> Option<usize> {
+    bytes
+        .windows(size)
+        .enumerate()
+        .find(|(_, chars)| {
+            chars
+                .iter()
+                .enumerate()
+                .all(|(i, c)| chars.iter().skip(i + 1).all(|other| *c != *other))
+        })
+        .map(|(idx, _)| idx + size)
+}
+
+impl Problem for Day06 {
+    type Input = String;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn solve_part1(input: &<Self::Input as aoc_helpers::scaffold::Parse>::Parsed) -> Self::Part1 {
+        find_first_unique(input.as_bytes(), 4).expect("There should be a solution")
+    }
+
+    fn solve_part2(input: &<Self::Input as aoc_helpers::scaffold::Parse>::Parsed) -> Self::Part2 {
+        find_first_unique(input.as_bytes(), 14).expect("There should be a solution")
+    }
+}
@@ -0,0 +1,1732 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{Display, Write},
+    ops::Range,
+    path::Path,
+};
+
+use anyhow::Context;
+
+use aoc_helpers::prelude::*;
+use rematch::rematch;
+
+use crate::{bits::Bits, glob, human_size};
+
+pub const SIZE: usize = 70000000;
+pub const NEEDED: usize = 30000000;
+
+pub struct Day07;
+
+/// Line of a shell transcript. Names are made of ASCII letters, digits, `_`, `.` and `-`, entries
+/// named `.` or `..` parse but [`Arena::validate`] rejects them.
+#[derive(Clone, Debug)]
+#[allow(clippy::upper_case_acronyms)]
+#[rematch]
+pub enum CLI {
+    #[rematch(r"\$ cd /")]
+    ChangeDirRoot,
+    #[rematch(r"\$ cd \.\.")]
+    ChangeDirUp,
+    /// Relative or absolute path, possibly with several segments (`a/b`, `/a/../c`).
+    #[rematch(r"\$ cd ([A-Za-z0-9_./-]+)")]
+    ChangeDir(String),
+    #[rematch(r"\$ ls")]
+    List,
+    #[rematch(r"dir ([A-Za-z0-9_.-]+)")]
+    Directory { name: String },
+    #[rematch(r"([0-9]+) ([A-Za-z0-9_.-]+)")]
+    File { size: usize, name: String },
+}
+
+/// The tree of an [`Arena`] as nested maps, e.g. for comparing trees whatever order their
+/// nodes were added in. Deep trees have to be dropped with [`FS::dismantle`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FS {
+    File(usize),
+    Directory(HashMap<String, FS>),
+}
+
+impl Default for FS {
+    fn default() -> Self {
+        Self::Directory(Default::default())
+    }
+}
+
+impl FS {
+    /// Replays a shell transcript, see [`Arena::from_cli_lines`].
+    pub fn from_cli_lines(lines: &[CLI]) -> Option<Self> {
+        Arena::from_cli_lines(lines).map(|arena| arena.to_fs())
+    }
+
+    pub fn size(&self) -> usize {
+        self.size_walk(&mut |_, _, _| {})
+    }
+
+    pub fn is_dir(&self) -> bool {
+        matches!(self, FS::Directory(_))
+    }
+
+    /// Drops the tree taking nested directories apart first, as dropping it as usual recurses
+    /// once per level and overflows the stack for deep trees.
+    pub fn dismantle(self) {
+        let mut stack = vec![self];
+        while let Some(fs) = stack.pop() {
+            if let FS::Directory(dir) = fs {
+                stack.extend(dir.into_values());
+            }
+        }
+    }
+
+    /// Calls `fun` with the path, the entry and its total size for every entry, directories
+    /// after all their entries. Returns the total size.
+    ///
+    /// Works for trees of any depth, it goes through an [`Arena`] walked with an explicit stack.
+    pub fn size_walk<F: FnMut(&str, &Self, usize)>(&self, fun: &mut F) -> usize {
+        if let FS::File(size) = self {
+            fun("/", self, *size);
+            return *size;
+        }
+        let (arena, entries) = Arena::from_fs_entries(self);
+        arena.walk(&mut |step| match step {
+            Step::Enter { path, node, .. } if !arena.is_dir(node) => {
+                fun(path, entries[node.0], arena.size(node))
+            }
+            Step::Leave { path, node, .. } => fun(path, entries[node.0], arena.size(node)),
+            _ => {}
+        });
+        arena.size(Arena::ROOT)
+    }
+}
+
+/// Problem found by [`Arena::validate`], `line` is 1-based.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Issue {
+    pub line: usize,
+    pub kind: IssueKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum IssueKind {
+    /// `cd` into a directory no `ls` showed, it's created empty.
+    UnknownDirectory { path: String },
+    /// A file listed again with a different size, the new size is kept.
+    SizeChanged {
+        path: String,
+        old: usize,
+        new: usize,
+    },
+    /// A directory that was never listed, so its size is unknown. The issue's line is where
+    /// the directory first came up.
+    NeverListed { path: String },
+    /// `cd ..` in `/`, which stays in `/`.
+    CdUpAtRoot,
+    /// A name used both for a file and a directory, the first one is kept.
+    Conflict { path: String },
+    /// An entry listed as `.` or `..`, which `cd` can't tell from the current and parent
+    /// directory. It's skipped.
+    ReservedName { name: String },
+}
+
+impl IssueKind {
+    /// Whether the transcript can't describe a filesystem at all, as opposed to an incomplete one.
+    pub fn is_error(&self) -> bool {
+        matches!(
+            self,
+            IssueKind::CdUpAtRoot | IssueKind::Conflict { .. } | IssueKind::ReservedName { .. }
+        )
+    }
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match &self.kind {
+            IssueKind::UnknownDirectory { path } => {
+                write!(f, "cd into {}, which wasn't listed", path)
+            }
+            IssueKind::SizeChanged { path, old, new } => {
+                write!(f, "{} listed with size {}, was {}", path, new, old)
+            }
+            IssueKind::NeverListed { path } => {
+                write!(f, "{} was never listed, its size is unknown", path)
+            }
+            IssueKind::CdUpAtRoot => f.write_str("cd .. in /"),
+            IssueKind::Conflict { path } => write!(f, "{} is both a file and a directory", path),
+            IssueKind::ReservedName { name } => write!(f, "{} can't be the name of an entry", name),
+        }
+    }
+}
+
+/// Filesystem replayed by [`Arena::validate`] despite the issues.
+#[derive(Clone, Debug)]
+pub struct Validation {
+    pub arena: Arena,
+    pub issues: Vec<Issue>,
+}
+
+impl Display for Validation {
+    /// One issue per line.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for issue in &self.issues {
+            writeln!(f, "{}", issue)?;
+        }
+        Ok(())
+    }
+}
+
+/// Whether `name` matches the names of the [`CLI`] grammar (`[A-Za-z0-9_.-]+`) and isn't `.` or
+/// `..`, which `cd` would take for the current and parent directory.
+fn is_transcript_name(name: &str) -> bool {
+    !matches!(name, "" | "." | "..")
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'))
+}
+
+/// Index of a node in an [`Arena`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(usize);
+
+/// Index of a name interned in an [`Arena`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct NameId(usize);
+
+#[derive(Clone, Debug)]
+struct Node {
+    parent: Option<NodeId>,
+    name: NameId,
+    /// Size of a file, or the total size of a directory once the arena is built.
+    size: usize,
+    /// `None` for a file.
+    children: Option<HashMap<NameId, NodeId>>,
+}
+
+/// Filesystem as a flat list of nodes referring to each other by [`NodeId`], with parent links,
+/// names stored once however often they come up and directory sizes computed up front.
+///
+/// It can go up from any node (`..` in [`Arena::resolve`]) and [`Arena::size`] takes constant
+/// time. Nodes come after their parent, the root being [`Arena::ROOT`]. Passing a [`NodeId`] of
+/// another arena gives wrong results or panics. [`Arena::to_fs`] gives the same tree as a
+/// nested [`FS`].
+#[derive(Clone, Debug)]
+pub struct Arena {
+    nodes: Vec<Node>,
+    names: Vec<String>,
+    name_ids: HashMap<String, NameId>,
+}
+
+impl Default for Arena {
+    /// Just the empty root directory.
+    fn default() -> Self {
+        let mut arena = Self {
+            nodes: Vec::new(),
+            names: Vec::new(),
+            name_ids: HashMap::new(),
+        };
+        // no entry can be called `/`, so the root's name doesn't clash with any other
+        let name = arena.intern("/");
+        arena.nodes.push(Node {
+            parent: None,
+            name,
+            size: 0,
+            children: Some(HashMap::new()),
+        });
+        arena
+    }
+}
+
+impl Arena {
+    pub const ROOT: NodeId = NodeId(0);
+
+    /// Replays a shell transcript, starting in `/`.
+    ///
+    /// `cd` into a directory that wasn't listed creates it and listing a directory again only
+    /// adds entries (and updates file sizes). Fails on the issues [`IssueKind::is_error`]
+    /// considers errors, see [`Arena::validate`] for finding out what went wrong.
+    pub fn from_cli_lines(lines: &[CLI]) -> Option<Self> {
+        let validation = Self::validate(lines);
+        if validation.issues.iter().any(|issue| issue.kind.is_error()) {
+            None
+        } else {
+            Some(validation.arena)
+        }
+    }
+
+    /// Replays a shell transcript like [`Arena::from_cli_lines`] but carries on past problems,
+    /// reporting each of them with its line number. `cd` takes the same time however deep the
+    /// tree is.
+    pub fn validate(lines: &[CLI]) -> Validation {
+        let mut arena = Self::default();
+        let mut cwd = Self::ROOT;
+        let mut issues = Vec::new();
+        // line where each directory came up first and which directories were listed
+        let mut first_seen: HashMap<NodeId, usize> = HashMap::new();
+        let mut listed: HashSet<NodeId> = HashSet::new();
+        if !lines.is_empty() {
+            first_seen.insert(Self::ROOT, 1);
+        }
+
+        for (idx, line) in lines.iter().enumerate() {
+            let line_number = idx + 1;
+            let mut issue = |kind| {
+                issues.push(Issue {
+                    line: line_number,
+                    kind,
+                })
+            };
+            match line {
+                CLI::ChangeDirRoot => cwd = Self::ROOT,
+                CLI::ChangeDirUp => match arena.parent(cwd) {
+                    Some(parent) => cwd = parent,
+                    None => issue(IssueKind::CdUpAtRoot),
+                },
+                CLI::ChangeDir(path) => {
+                    let previous_cwd = cwd;
+                    if path.starts_with('/') {
+                        cwd = Self::ROOT;
+                    }
+                    for name in path.split('/').filter(|name| !matches!(*name, "" | ".")) {
+                        if name == ".." {
+                            match arena.parent(cwd) {
+                                Some(parent) => cwd = parent,
+                                None => issue(IssueKind::CdUpAtRoot),
+                            }
+                            continue;
+                        }
+                        match arena.child(cwd, name) {
+                            Some(child) if arena.is_dir(child) => cwd = child,
+                            Some(_) => {
+                                issue(IssueKind::Conflict {
+                                    path: arena.child_path(cwd, name),
+                                });
+                                cwd = previous_cwd;
+                                break;
+                            }
+                            None => {
+                                cwd = arena.add(cwd, name, None);
+                                first_seen.insert(cwd, line_number);
+                                issue(IssueKind::UnknownDirectory {
+                                    path: arena.path(cwd),
+                                });
+                            }
+                        }
+                    }
+                }
+                CLI::List => {
+                    listed.insert(cwd);
+                }
+                CLI::Directory { name } | CLI::File { name, .. }
+                    if matches!(name.as_str(), "." | "..") =>
+                {
+                    issue(IssueKind::ReservedName { name: name.clone() })
+                }
+                CLI::Directory { name } => match arena.child(cwd, name) {
+                    None => {
+                        let dir = arena.add(cwd, name, None);
+                        first_seen.insert(dir, line_number);
+                    }
+                    Some(child) if arena.is_dir(child) => {}
+                    Some(_) => issue(IssueKind::Conflict {
+                        path: arena.child_path(cwd, name),
+                    }),
+                },
+                CLI::File { size, name } => match arena.child(cwd, name) {
+                    None => {
+                        arena.add(cwd, name, Some(*size));
+                    }
+                    Some(child) if arena.is_dir(child) => issue(IssueKind::Conflict {
+                        path: arena.child_path(cwd, name),
+                    }),
+                    Some(child) => {
+                        let old_size = std::mem::replace(&mut arena.nodes[child.0].size, *size);
+                        if old_size != *size {
+                            issue(IssueKind::SizeChanged {
+                                path: arena.child_path(cwd, name),
+                                old: old_size,
+                                new: *size,
+                            });
+                        }
+                    }
+                },
+            }
+        }
+        arena.compute_sizes();
+
+        let mut never_listed: Vec<Issue> = first_seen
+            .into_iter()
+            .filter(|(dir, _)| !listed.contains(dir))
+            .map(|(dir, line)| Issue {
+                line,
+                kind: IssueKind::NeverListed {
+                    path: arena.path(dir),
+                },
+            })
+            .collect();
+        never_listed.sort_unstable_by(|a, b| (a.line, &a.kind).cmp(&(b.line, &b.kind)));
+        issues.extend(never_listed);
+        issues.sort_by_key(|issue| issue.line);
+        Validation { arena, issues }
+    }
+
+    /// Reads a directory from disk, with file sizes in bytes.
+    ///
+    /// Symbolic links and anything else that isn't a file or a directory are skipped. Fails on
+    /// names a transcript can't hold, see [`Arena::to_transcript`].
+    pub fn from_path(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let mut arena = Self::default();
+        arena.read_dir(Self::ROOT, path.as_ref())?;
+        arena.compute_sizes();
+        Ok(arena)
+    }
+
+    /// Adds the entries of the directory at `path` to `dir`, see [`Arena::from_path`].
+    fn read_dir(&mut self, dir: NodeId, path: &Path) -> anyhow::Result<()> {
+        for entry in
+            std::fs::read_dir(path).with_context(|| format!("Can't list {}", path.display()))?
+        {
+            let entry = entry.with_context(|| format!("Can't list {}", path.display()))?;
+            let entry_path = entry.path();
+            let name = entry
+                .file_name()
+                .into_string()
+                .ok()
+                .filter(|name| is_transcript_name(name))
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Name of {} can't be written in a transcript",
+                        entry_path.display()
+                    )
+                })?;
+            let file_type = entry
+                .file_type()
+                .with_context(|| format!("Can't read {}", entry_path.display()))?;
+            if file_type.is_dir() {
+                let child = self.add(dir, &name, None);
+                self.read_dir(child, &entry_path)?;
+            } else if file_type.is_file() {
+                let size = entry
+                    .metadata()
+                    .with_context(|| format!("Can't read {}", entry_path.display()))?
+                    .len();
+                self.add(dir, &name, Some(size.try_into()?));
+            }
+        }
+        Ok(())
+    }
+
+    /// The root is always a directory, so a lone [`FS::File`] turns into an empty one.
+    pub fn from_fs(fs: &FS) -> Self {
+        Self::from_fs_entries(fs).0
+    }
+
+    /// [`Arena::from_fs`] along with the entry of `fs` each node was made from.
+    fn from_fs_entries(fs: &FS) -> (Self, Vec<&FS>) {
+        let mut arena = Self::default();
+        let mut entries = vec![fs];
+        // directories whose entries are still to be added
+        let mut todo = vec![Self::ROOT];
+        while let Some(dir) = todo.pop() {
+            if let FS::Directory(children) = entries[dir.0] {
+                for (name, child) in children {
+                    let node = arena.add(
+                        dir,
+                        name,
+                        match child {
+                            FS::File(size) => Some(*size),
+                            FS::Directory(_) => None,
+                        },
+                    );
+                    if child.is_dir() {
+                        todo.push(node);
+                    }
+                    entries.push(child);
+                }
+            }
+        }
+        arena.compute_sizes();
+        (arena, entries)
+    }
+
+    /// The same tree as a nested [`FS`], built bottom-up without recursion.
+    pub fn to_fs(&self) -> FS {
+        let mut built: Vec<HashMap<String, FS>> = Vec::new();
+        built.resize_with(self.nodes.len(), HashMap::new);
+        for (idx, node) in self.nodes.iter().enumerate().rev() {
+            if let Some(children) = &node.children {
+                built[idx] = children
+                    .iter()
+                    .map(|(name, child)| {
+                        let fs = match &self.nodes[child.0].children {
+                            None => FS::File(self.nodes[child.0].size),
+                            Some(_) => FS::Directory(std::mem::take(&mut built[child.0])),
+                        };
+                        (self.names[name.0].clone(), fs)
+                    })
+                    .collect();
+            }
+        }
+        FS::Directory(std::mem::take(&mut built[0]))
+    }
+
+    /// Shell transcript that [`Arena::from_cli_lines`] turns back into this filesystem.
+    ///
+    /// Every directory is listed (entries sorted by name) and then entered, in the same order,
+    /// returning with `$ cd ..`. Names have to fit the [`CLI`] grammar.
+    pub fn to_transcript(&self) -> String {
+        let mut out = "$ cd /\n".to_owned();
+        self.walk(&mut |step| match step {
+            Step::Enter { depth, node, .. } if self.is_dir(node) => {
+                if depth > 0 {
+                    writeln!(out, "$ cd {}", self.name(node)).unwrap();
+                }
+                out.push_str("$ ls\n");
+                for child in self.children(node, SortBy::Name) {
+                    if self.is_dir(child) {
+                        writeln!(out, "dir {}", self.name(child))
+                    } else {
+                        writeln!(out, "{} {}", self.size(child), self.name(child))
+                    }
+                    .unwrap();
+                }
+            }
+            Step::Leave { depth, .. } if depth > 0 => out.push_str("$ cd ..\n"),
+            _ => {}
+        });
+        out
+    }
+
+    fn intern(&mut self, name: &str) -> NameId {
+        if let Some(id) = self.name_ids.get(name) {
+            return *id;
+        }
+        let id = NameId(self.names.len());
+        self.names.push(name.to_owned());
+        self.name_ids.insert(name.to_owned(), id);
+        id
+    }
+
+    /// Adds a file (with its size) or a directory to `parent`, sizes of directories are only
+    /// right after [`Arena::compute_sizes`].
+    fn add(&mut self, parent: NodeId, name: &str, file_size: Option<usize>) -> NodeId {
+        let id = NodeId(self.nodes.len());
+        let name = self.intern(name);
+        self.nodes.push(Node {
+            parent: Some(parent),
+            name,
+            size: file_size.unwrap_or(0),
+            children: file_size.is_none().then(HashMap::new),
+        });
+        self.nodes[parent.0]
+            .children
+            .as_mut()
+            .expect("Entries should only be added to directories")
+            .insert(name, id);
+        id
+    }
+
+    /// Sums up directory sizes, children coming after their parent means a single backwards
+    /// pass sees every directory complete before its parent.
+    fn compute_sizes(&mut self) {
+        for node in &mut self.nodes {
+            if node.children.is_some() {
+                node.size = 0;
+            }
+        }
+        for idx in (1..self.nodes.len()).rev() {
+            if let Some(parent) = self.nodes[idx].parent {
+                self.nodes[parent.0].size += self.nodes[idx].size;
+            }
+        }
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Number of files in the whole tree.
+    pub fn file_count(&self) -> usize {
+        self.nodes
+            .iter()
+            .filter(|node| node.children.is_none())
+            .count()
+    }
+
+    /// All nodes, every one after its parent.
+    pub fn nodes(&self) -> impl Iterator<Item = NodeId> {
+        (0..self.nodes.len()).map(NodeId)
+    }
+
+    /// `None` for the root.
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.nodes[id.0].parent
+    }
+
+    /// Name of the entry, `/` for the root.
+    pub fn name(&self, id: NodeId) -> &str {
+        &self.names[self.nodes[id.0].name.0]
+    }
+
+    /// Size of a file or total size of a directory.
+    pub fn size(&self, id: NodeId) -> usize {
+        self.nodes[id.0].size
+    }
+
+    pub fn is_dir(&self, id: NodeId) -> bool {
+        self.nodes[id.0].children.is_some()
+    }
+
+    pub fn child(&self, id: NodeId, name: &str) -> Option<NodeId> {
+        let name = self.name_ids.get(name)?;
+        self.nodes[id.0].children.as_ref()?.get(name).copied()
+    }
+
+    /// Entries of a directory in the given order, empty for a file.
+    pub fn children(&self, id: NodeId, order: SortBy) -> Vec<NodeId> {
+        let mut children: Vec<NodeId> = self.nodes[id.0]
+            .children
+            .iter()
+            .flat_map(|children| children.values().copied())
+            .collect();
+        match order {
+            SortBy::Name => children.sort_unstable_by_key(|child| self.name(*child)),
+            SortBy::Size => children.sort_unstable_by_key(|child| {
+                (std::cmp::Reverse(self.size(*child)), self.name(*child))
+            }),
+        }
+        children
+    }
+
+    /// Entry at `path` relative to `from` (or absolute when it starts with `/`), following `.`
+    /// and `..` like `cd`. `None` if there's no such entry or `..` leaves the root.
+    pub fn resolve(&self, from: NodeId, path: &str) -> Option<NodeId> {
+        let start = if path.starts_with('/') {
+            Self::ROOT
+        } else {
+            from
+        };
+        path.split('/')
+            .filter(|name| !matches!(*name, "" | "."))
+            .try_fold(start, |node, name| match name {
+                ".." => self.parent(node),
+                _ => self.child(node, name),
+            })
+    }
+
+    /// Entry at an absolute path, see [`Arena::resolve`].
+    pub fn get(&self, path: &str) -> Option<NodeId> {
+        self.resolve(Self::ROOT, path)
+    }
+
+    /// Total size of the entry at `path`.
+    pub fn du(&self, path: &str) -> Option<usize> {
+        self.get(path).map(|node| self.size(node))
+    }
+
+    pub fn path(&self, id: NodeId) -> String {
+        let mut names = Vec::new();
+        let mut node = id;
+        while let Some(parent) = self.parent(node) {
+            names.push(self.name(node));
+            node = parent;
+        }
+        names.reverse();
+        format!("/{}", names.join("/"))
+    }
+
+    fn child_path(&self, id: NodeId, name: &str) -> String {
+        format!("{}/{}", self.path(id).trim_end_matches('/'), name)
+    }
+
+    /// Depth-first walk over every entry from the root, entries of a directory in name order.
+    ///
+    /// Open directories are kept on an explicit stack instead of recursing and the path is
+    /// only borrowed, so deep trees take linear time and memory.
+    fn walk(&self, fun: &mut impl FnMut(Step<'_>)) {
+        struct Open {
+            node: NodeId,
+            /// Entries still to walk, last one first.
+            entries: Vec<NodeId>,
+            parent_path_len: usize,
+        }
+
+        let mut path = String::new();
+        let mut stack: Vec<Open> = Vec::new();
+        let mut next = Some(Self::ROOT);
+        loop {
+            if let Some(node) = next.take() {
+                let parent_path_len = path.len();
+                if !path.ends_with('/') {
+                    path.push('/');
+                }
+                if !stack.is_empty() {
+                    path.push_str(self.name(node));
+                }
+                fun(Step::Enter {
+                    path: &path,
+                    depth: stack.len(),
+                    node,
+                });
+                if self.is_dir(node) {
+                    let mut entries = self.children(node, SortBy::Name);
+                    entries.reverse();
+                    stack.push(Open {
+                        node,
+                        entries,
+                        parent_path_len,
+                    });
+                } else {
+                    path.truncate(parent_path_len);
+                }
+            }
+            let Some(open) = stack.last_mut() else {
+                return;
+            };
+            match open.entries.pop() {
+                Some(child) => next = Some(child),
+                None => {
+                    let open = stack
+                        .pop()
+                        .expect("The open directory should be on the stack");
+                    fun(Step::Leave {
+                        path: &path,
+                        depth: stack.len(),
+                        node: open.node,
+                    });
+                    path.truncate(open.parent_path_len);
+                }
+            }
+        }
+    }
+
+    /// Files matching a glob pattern, sorted by path.
+    ///
+    /// A pattern with a `/` is matched against the whole path (`/a/*/i`, `**/*.txt`), otherwise
+    /// against the file name (`*.txt`). `*` and `?` don't match `/`, `**` matches anything.
+    pub fn find(&self, pattern: &str) -> Vec<(String, NodeId)> {
+        let mut found = Vec::new();
+        self.walk(&mut |step| {
+            if let Step::Enter { path, node, .. } = step {
+                if self.is_dir(node) {
+                    return;
+                }
+                let text = if pattern.contains('/') {
+                    path
+                } else {
+                    self.name(node)
+                };
+                let pattern = pattern.strip_prefix('/').unwrap_or(pattern);
+                let text = text.strip_prefix('/').unwrap_or(text);
+                if glob::matches(pattern, text) {
+                    found.push((path.to_owned(), node));
+                }
+            }
+        });
+        found.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+        found
+    }
+
+    /// Indented listing in the style of the puzzle text, entries sorted by name:
+    ///
+    /// ```text
+    /// - / (dir)
+    ///   - a (dir)
+    ///     - f (file, size=29116)
+    /// ```
+    pub fn render_tree(&self) -> String {
+        let mut out = String::new();
+        self.walk(&mut |step| {
+            if let Step::Enter { depth, node, .. } = step {
+                let indent = "  ".repeat(depth);
+                if self.is_dir(node) {
+                    writeln!(out, "{}- {} (dir)", indent, self.name(node))
+                } else {
+                    let (name, size) = (self.name(node), self.size(node));
+                    writeln!(out, "{}- {} (file, size={})", indent, name, size)
+                }
+                .unwrap();
+            }
+        });
+        out
+    }
+
+    /// Cumulative size and path of every directory, like `du -h`: subdirectories (sorted by
+    /// name) come before their parent and sizes are rounded up to one of `K`, `M`, `G`, ...
+    pub fn render_du(&self) -> String {
+        let mut out = String::new();
+        self.walk(&mut |step| {
+            if let Step::Leave { path, node, .. } = step {
+                writeln!(out, "{}\t{}", human_size::format(self.size(node)), path).unwrap();
+            }
+        });
+        out
+    }
+
+    /// All directories in preorder (children by name).
+    fn directories(&self) -> Vec<DirectoryInfo> {
+        let mut dirs = Vec::new();
+        let mut open: Vec<usize> = Vec::new();
+        self.walk(&mut |step| match step {
+            Step::Enter { depth, node, .. } if self.is_dir(node) => {
+                open.push(dirs.len());
+                dirs.push(DirectoryInfo {
+                    node,
+                    depth,
+                    subtree_end: 0,
+                });
+            }
+            Step::Leave { .. } => {
+                let idx = open
+                    .pop()
+                    .expect("Directories should be left after entering");
+                dirs[idx].subtree_end = dirs.len();
+            }
+            _ => {}
+        });
+        dirs
+    }
+}
+
+/// Step of [`Arena::walk`].
+enum Step<'p> {
+    /// Any entry, before the entries of a directory.
+    Enter {
+        path: &'p str,
+        depth: usize,
+        node: NodeId,
+    },
+    /// A directory after all its entries.
+    Leave {
+        path: &'p str,
+        depth: usize,
+        node: NodeId,
+    },
+}
+
+/// Directory of [`Arena::directories`].
+struct DirectoryInfo {
+    node: NodeId,
+    /// 0 for the root.
+    depth: usize,
+    /// Index (in preorder) of the first directory after this one's subtree.
+    subtree_end: usize,
+}
+
+/// Directory to delete and how much space that frees.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Deletion {
+    pub path: String,
+    pub size: usize,
+}
+
+/// Picks directories to delete so that a disk of `capacity` has `required` free space.
+///
+/// The default is the disk of the puzzle, [`SIZE`] and [`NEEDED`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cleanup {
+    pub capacity: usize,
+    pub required: usize,
+}
+
+impl Default for Cleanup {
+    fn default() -> Self {
+        Self {
+            capacity: SIZE,
+            required: NEEDED,
+        }
+    }
+}
+
+impl Cleanup {
+    /// How much space has to be freed, 0 if there's enough already.
+    pub fn to_free(&self, arena: &Arena) -> usize {
+        self.required
+            .saturating_sub(self.capacity.saturating_sub(arena.size(Arena::ROOT)))
+    }
+
+    /// The smallest directory that frees enough on its own (part 2), the first path on ties.
+    ///
+    /// `None` if even deleting `/` isn't enough.
+    pub fn smallest_directory(&self, arena: &Arena) -> Option<Deletion> {
+        let to_free = self.to_free(arena);
+        let dirs = || arena.nodes().filter(|node| arena.is_dir(*node));
+        let size = dirs()
+            .map(|dir| arena.size(dir))
+            .filter(|size| *size >= to_free)
+            .min()?;
+        let path = dirs()
+            .filter(|dir| arena.size(*dir) == size)
+            .map(|dir| arena.path(dir))
+            .min()
+            .expect("The smallest size should belong to a directory");
+        Some(Deletion { path, size })
+    }
+
+    /// Directories, none inside another, that free enough with the smallest total size.
+    ///
+    /// Empty if nothing has to be freed, `None` if even deleting `/` isn't enough. Fails if the
+    /// bitsets below would take more than [`MINIMAL_SET_BITS`].
+    ///
+    /// This is subset sum over the directory tree. Totals only go up to the smallest directory
+    /// freeing enough on its own, as no set can beat it, and they're found with bitsets in a
+    /// sweep over the directories: `depth + 3` of them are alive at once, `limit / 8` bytes
+    /// each. The set is then rebuilt by splitting runs of subtrees in two and finding how the
+    /// total splits between both halves, which sweeps each directory again for every split it's
+    /// in, with bitsets only as long as the total of the run.
+    pub fn minimal_set(&self, arena: &Arena) -> anyhow::Result<Option<Vec<Deletion>>> {
+        let to_free = self.to_free(arena);
+        let Some(single) = self.smallest_directory(arena) else {
+            return Ok(None);
+        };
+        if to_free == 0 {
+            return Ok(Some(Vec::new()));
+        }
+        let dirs = arena.directories();
+        let size = |idx: usize| arena.size(dirs[idx].node);
+        let limit = single.size;
+        // the pending deletions of a sweep, its current totals and the other half's totals
+        let depth = dirs.iter().map(|dir| dir.depth).max().unwrap_or(0);
+        if (depth + 3).saturating_mul(limit + 1) > MINIMAL_SET_BITS {
+            anyhow::bail!(
+                "Finding the minimal set of directories to free {} would take more than {} MiB",
+                to_free,
+                MINIMAL_SET_BITS >> 23
+            );
+        }
+        let totals = |range, limit| Self::totals(&dirs, range, size, limit);
+
+        let all = totals(0..dirs.len(), limit);
+        let total = (to_free..=limit)
+            .find(|total| all.get(*total))
+            .expect("The smallest directory should be reachable");
+        if total == single.size {
+            return Ok(Some(vec![single]));
+        }
+        // runs of whole subtrees with the total to delete in them
+        let mut todo = vec![(0..dirs.len(), total)];
+        let mut deleted = Vec::new();
+        while let Some((range, total)) = todo.pop() {
+            if total == 0 {
+                continue;
+            }
+            let roots: Vec<usize> = std::iter::successors(Some(range.start), |idx| {
+                Some(dirs[*idx].subtree_end).filter(|end| *end < range.end)
+            })
+            .collect();
+            if let [root] = roots[..] {
+                if size(root) == total {
+                    deleted.push(root);
+                } else {
+                    todo.push((root + 1..range.end, total));
+                }
+                continue;
+            }
+            // two runs with about as many directories each
+            let middle = (range.start + range.end) / 2;
+            let split = *roots[1..]
+                .iter()
+                .min_by_key(|idx| idx.abs_diff(middle))
+                .expect("A run of several subtrees should have several roots");
+            let in_first = totals(range.start..split, total)
+                .first_sum(&totals(split..range.end, total), total)
+                .expect("A reachable total should split between both runs");
+            todo.push((range.start..split, in_first));
+            todo.push((split..range.end, total - in_first));
+        }
+        deleted.sort_unstable();
+        Ok(Some(
+            deleted
+                .into_iter()
+                .map(|idx| Deletion {
+                    path: arena.path(dirs[idx].node),
+                    size: size(idx),
+                })
+                .collect(),
+        ))
+    }
+
+    /// Totals up to `limit` of the sets of directories in `dirs[range]` none of which is inside
+    /// another, `range` being a run of whole subtrees.
+    ///
+    /// Directories are in preorder, so the ones not inside a directory come right after its
+    /// subtree. Walking them, each one is either entered or deleted, which skips its subtree.
+    /// Totals with deletions are kept until the end of the deleted subtree, merged with those
+    /// of enclosing directories ending at the same place.
+    fn totals(
+        dirs: &[DirectoryInfo],
+        range: Range<usize>,
+        size: impl Fn(usize) -> usize,
+        limit: usize,
+    ) -> Bits {
+        let mut current = Bits::new(limit + 1);
+        current.set(0);
+        // where the deleted subtrees end and the totals with them, innermost last
+        let mut pending: Vec<(usize, Bits)> = Vec::new();
+        for idx in range {
+            if pending.last().is_some_and(|(end, _)| *end == idx) {
+                let (_, deleted) = pending.pop().expect("The subtree should be pending");
+                current.or_shifted(&deleted, 0);
+            }
+            let end = dirs[idx].subtree_end;
+            if pending
+                .last()
+                .is_none_or(|(pending_end, _)| *pending_end != end)
+            {
+                pending.push((end, Bits::new(limit + 1)));
+            }
+            let (_, deleted) = pending.last_mut().expect("The subtree should be pending");
+            deleted.or_shifted(&current, size(idx));
+        }
+        // every subtree ends with the run
+        for (_, deleted) in pending {
+            current.or_shifted(&deleted, 0);
+        }
+        current
+    }
+}
+
+/// Bits the bitsets of [`Cleanup::minimal_set`] may take together (512 MiB).
+pub const MINIMAL_SET_BITS: usize = 1 << 32;
+
+/// Order of [`Arena::children`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortBy {
+    Name,
+    /// Largest first, entries of the same size by name.
+    Size,
+}
+
+impl Problem for Day07 {
+    type Input = VecFromLines<CLI>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn solve_part1(input: &<Self::Input as aoc_helpers::scaffold::Parse>::Parsed) -> Self::Part1 {
+        let arena = Arena::from_cli_lines(input).expect("Input should be valid");
+        arena
+            .nodes()
+            .filter(|node| arena.is_dir(*node))
+            .map(|dir| arena.size(dir))
+            .filter(|size| *size <= 100000)
+            .sum()
+    }
+
+    fn solve_part2(input: &<Self::Input as aoc_helpers::scaffold::Parse>::Parsed) -> Self::Part2 {
+        let arena = Arena::from_cli_lines(input).expect("Input should be valid");
+        Cleanup::default()
+            .smallest_directory(&arena)
+            .expect("Some directory should be large enough")
+            .size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_helpers::scaffold::{solve_part1, solve_part2, Parse};
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    const SAMPLE: &str = include_str!("../../inputs/samples/day07-sample.txt");
+
+    fn sample_arena() -> Arena {
+        Arena::from_cli_lines(&VecFromLines::<CLI>::parse(SAMPLE).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_paths() {
+        let arena = sample_arena();
+        assert_eq!(arena.du("/"), Some(48381165));
+        assert_eq!(arena.du("/a/e"), Some(584));
+        assert_eq!(arena.du("a/e/"), Some(584));
+        assert_eq!(arena.du("/d"), Some(24933642));
+        assert_eq!(arena.du("/b.txt"), Some(14848514));
+        let i = arena.get("/a/e/i").unwrap();
+        assert!(!arena.is_dir(i));
+        assert_eq!(arena.size(i), 584);
+        assert!(arena.get("/a/x").is_none());
+        assert!(arena.get("/b.txt/x").is_none());
+        assert_eq!(arena.file_count(), 10);
+    }
+
+    #[test]
+    fn test_children() {
+        let arena = sample_arena();
+        let names = |order| {
+            arena
+                .children(Arena::ROOT, order)
+                .into_iter()
+                .map(|child| arena.name(child))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(SortBy::Name), vec!["a", "b.txt", "c.dat", "d"]);
+        assert_eq!(names(SortBy::Size), vec!["d", "b.txt", "c.dat", "a"]);
+        let e = arena.get("/a/e").unwrap();
+        assert!(arena
+            .children(e, SortBy::Name)
+            .iter()
+            .all(|child| arena.parent(*child) == Some(e)));
+        let b = arena.get("/b.txt").unwrap();
+        assert!(arena.children(b, SortBy::Name).is_empty());
+    }
+
+    #[test]
+    fn test_find() {
+        let arena = sample_arena();
+        let paths = |pattern| {
+            arena
+                .find(pattern)
+                .into_iter()
+                .map(|(path, _)| path)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            paths("*.*"),
+            vec!["/a/h.lst", "/b.txt", "/c.dat", "/d/d.ext", "/d/d.log"]
+        );
+        assert_eq!(paths("d.*"), vec!["/d/d.ext", "/d/d.log"]);
+        assert_eq!(paths("?"), vec!["/a/e/i", "/a/f", "/a/g", "/d/j", "/d/k"]);
+        assert_eq!(paths("/a/*"), vec!["/a/f", "/a/g", "/a/h.lst"]);
+        assert_eq!(paths("/a/**"), vec!["/a/e/i", "/a/f", "/a/g", "/a/h.lst"]);
+        assert_eq!(paths("**/i"), vec!["/a/e/i"]);
+        assert!(paths("*.rs").is_empty());
+    }
+
+    #[test]
+    fn test_render_tree() {
+        assert_eq!(
+            sample_arena().render_tree(),
+            concat!(
+                "- / (dir)\n",
+                "  - a (dir)\n",
+                "    - e (dir)\n",
+                "      - i (file, size=584)\n",
+                "    - f (file, size=29116)\n",
+                "    - g (file, size=2557)\n",
+                "    - h.lst (file, size=62596)\n",
+                "  - b.txt (file, size=14848514)\n",
+                "  - c.dat (file, size=8504156)\n",
+                "  - d (dir)\n",
+                "    - d.ext (file, size=5626152)\n",
+                "    - d.log (file, size=8033020)\n",
+                "    - j (file, size=4060174)\n",
+                "    - k (file, size=7214296)\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_render_du() {
+        assert_eq!(
+            sample_arena().render_du(),
+            concat!("584\t/a/e\n", "93K\t/a\n", "24M\t/d\n", "47M\t/\n")
+        );
+    }
+
+    fn arena(transcript: &str) -> Option<Arena> {
+        Arena::from_cli_lines(&VecFromLines::<CLI>::parse(transcript).unwrap())
+    }
+
+    #[test]
+    fn test_transcript_grammar() {
+        let arena = arena(concat!(
+            "$ ls\n",
+            "dir src-2\n",
+            "10 README_1.md\n",
+            "$ cd src-2/lib_3\n",
+            "$ ls\n",
+            "20 mod.rs\n",
+            "$ cd /\n",
+            "$ ls\n",
+            "dir src-2\n",
+            "10 README_1.md\n",
+            "$ cd /src-2/lib_3/../bin\n",
+            "$ ls\n",
+            "30 main\n",
+            "$ cd /\n",
+            "$ cd src-2\n",
+            "$ ls\n",
+            "dir lib_3\n",
+            "dir bin\n",
+            "$ cd ..\n",
+        ))
+        .unwrap();
+        assert_eq!(arena.du("/"), Some(60));
+        assert_eq!(arena.du("/src-2/lib_3/mod.rs"), Some(20));
+        assert_eq!(arena.du("/src-2/bin"), Some(30));
+        assert_eq!(arena.file_count(), 3);
+
+        // the grammar and `from_path` agree on names
+        for name in ["é", "a b", "x+y", "٣"] {
+            assert!(VecFromLines::<CLI>::parse(&format!("dir {}\n", name)).is_err());
+            assert!(VecFromLines::<CLI>::parse(&format!("1 {}\n", name)).is_err());
+            assert!(!is_transcript_name(name), "{}", name);
+        }
+        assert!(VecFromLines::<CLI>::parse("٣ a\n").is_err());
+        assert!(is_transcript_name("A-z_0.9"));
+    }
+
+    #[test]
+    fn test_transcript_errors() {
+        assert!(arena("$ cd /\n$ cd ..\n").is_none());
+        assert!(arena("$ cd a/../..\n").is_none());
+        assert!(arena("$ ls\n10 a\n$ cd a\n").is_none());
+        assert!(arena("$ ls\n10 a\ndir a\n").is_none());
+        assert!(arena("$ ls\ndir a\n10 a\n").is_none());
+    }
+
+    #[test]
+    fn test_validate() {
+        let lines = VecFromLines::<CLI>::parse(concat!(
+            "$ cd /\n",
+            "$ ls\n",
+            "dir a\n",
+            "dir b\n",
+            "10 f\n",
+            "$ cd x/y\n",
+            "$ cd /\n",
+            "$ ls\n",
+            "11 f\n",
+            "dir f\n",
+            "$ cd ..\n",
+            "$ cd f\n",
+            "$ cd b\n",
+            "$ ls\n",
+        ))
+        .unwrap();
+        let validation = Arena::validate(&lines);
+        assert_eq!(
+            validation.to_string(),
+            concat!(
+                "line 3: /a was never listed, its size is unknown\n",
+                "line 6: cd into /x, which wasn't listed\n",
+                "line 6: cd into /x/y, which wasn't listed\n",
+                "line 6: /x was never listed, its size is unknown\n",
+                "line 6: /x/y was never listed, its size is unknown\n",
+                "line 9: /f listed with size 11, was 10\n",
+                "line 10: /f is both a file and a directory\n",
+                "line 11: cd .. in /\n",
+                "line 12: /f is both a file and a directory\n",
+            )
+        );
+        assert_eq!(validation.arena.du("/"), Some(11));
+        assert!(Arena::from_cli_lines(&lines).is_none());
+
+        let lines = VecFromLines::<CLI>::parse("$ cd a\n$ ls\n1 f\n").unwrap();
+        assert_eq!(
+            Arena::validate(&lines)
+                .issues
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec![
+                "line 1: cd into /a, which wasn't listed",
+                "line 1: / was never listed, its size is unknown",
+            ]
+        );
+        assert!(Arena::from_cli_lines(&lines).is_some());
+
+        let sample = VecFromLines::<CLI>::parse(SAMPLE).unwrap();
+        assert!(Arena::validate(&sample).issues.is_empty());
+    }
+
+    #[test]
+    fn test_validate_reserved_directory_name() {
+        let lines = VecFromLines::<CLI>::parse(
+            "$ ls
+dir ..
+dir a
+$ cd a
+$ ls
+",
+        )
+        .unwrap();
+        let validation = Arena::validate(&lines);
+        assert_eq!(
+            validation.issues,
+            vec![Issue {
+                line: 2,
+                kind: IssueKind::ReservedName {
+                    name: "..".to_string()
+                },
+            }]
+        );
+        assert_eq!(
+            validation.to_string(),
+            "line 2: .. can't be the name of an entry\n"
+        );
+        assert_eq!(validation.arena.node_count(), 2);
+        assert!(Arena::from_cli_lines(&lines).is_none());
+    }
+
+    #[test]
+    fn test_validate_reserved_file_name() {
+        let lines = VecFromLines::<CLI>::parse(
+            "$ ls
+1 .
+2 f
+",
+        )
+        .unwrap();
+        let validation = Arena::validate(&lines);
+        assert_eq!(
+            validation.issues,
+            vec![Issue {
+                line: 2,
+                kind: IssueKind::ReservedName {
+                    name: ".".to_string()
+                },
+            }]
+        );
+        assert_eq!(validation.arena.du("/"), Some(2));
+        assert!(Arena::from_cli_lines(&lines).is_none());
+    }
+
+    #[test]
+    fn test_arena() {
+        let lines = VecFromLines::<CLI>::parse(SAMPLE).unwrap();
+        let arena = Arena::from_cli_lines(&lines).unwrap();
+        assert_eq!(arena.node_count(), 14);
+        assert_eq!(arena.name(Arena::ROOT), "/");
+        assert_eq!(arena.parent(Arena::ROOT), None);
+        assert_eq!(arena.size(Arena::ROOT), 48381165);
+
+        let e = arena.get("/a/e").unwrap();
+        assert!(arena.is_dir(e));
+        assert_eq!(arena.name(e), "e");
+        assert_eq!(arena.size(e), 584);
+        assert_eq!(arena.path(e), "/a/e");
+        assert_eq!(arena.parent(e), arena.get("a"));
+        assert_eq!(arena.resolve(e, "../../d/j"), arena.get("/d/j"));
+        assert_eq!(arena.resolve(e, "./i"), arena.child(e, "i"));
+        assert_eq!(arena.resolve(e, "/"), Some(Arena::ROOT));
+        assert_eq!(arena.resolve(e, "../../.."), None);
+        assert_eq!(arena.get("/a/e/i/x"), None);
+        assert_eq!(arena.size(arena.get("/d/k").unwrap()), 7214296);
+        assert!(!arena.is_dir(arena.get("/d/k").unwrap()));
+
+        assert!(arena
+            .nodes()
+            .skip(1)
+            .all(|node| arena.parent(node) < Some(node)));
+
+        let fs = arena.to_fs();
+        assert_eq!(fs.size(), 48381165);
+        assert!(matches!(&fs, FS::Directory(dir) if dir.len() == 4));
+        assert_eq!(FS::from_cli_lines(&lines), Some(fs.clone()));
+        let arena = Arena::from_fs(&fs);
+        assert_eq!(arena.to_fs(), fs);
+        assert_eq!(arena.size(arena.get("/a").unwrap()), 94853);
+        assert_eq!(Arena::from_fs(&FS::File(1)).to_fs(), FS::default());
+
+        // entries can be moved out
+        let FS::Directory(mut dir) = fs else {
+            panic!("The root should be a directory");
+        };
+        assert!(matches!(dir.remove("d"), Some(FS::Directory(d)) if d.len() == 4));
+    }
+
+    #[test]
+    fn test_arena_interns_names() {
+        let transcript = "$ ls\ndir d\n1 f\n$ cd d\n".repeat(100) + "$ ls\n";
+        let arena =
+            Arena::from_cli_lines(&VecFromLines::<CLI>::parse(&transcript).unwrap()).unwrap();
+        assert_eq!(arena.node_count(), 201);
+        assert_eq!(arena.names.len(), 3);
+        assert_eq!(arena.size(Arena::ROOT), 100);
+
+        let lines = VecFromLines::<CLI>::parse("$ ls\n1 f\n$ cd ..\n").unwrap();
+        assert!(Arena::from_cli_lines(&lines).is_none());
+        let lines = VecFromLines::<CLI>::parse("$ ls\n1 f\n$ ls\n5 f\n").unwrap();
+        assert_eq!(Arena::from_cli_lines(&lines).unwrap().size(Arena::ROOT), 5);
+    }
+
+    #[test]
+    fn test_cleanup() {
+        let arena = sample_arena();
+        let cleanup = Cleanup::default();
+        assert_eq!(cleanup.to_free(&arena), 8381165);
+        let deletion = Deletion {
+            path: "/d".to_owned(),
+            size: 24933642,
+        };
+        assert_eq!(cleanup.smallest_directory(&arena), Some(deletion.clone()));
+        assert_eq!(cleanup.minimal_set(&arena).unwrap(), Some(vec![deletion]));
+
+        let cleanup = Cleanup {
+            capacity: 48381165,
+            required: 90000,
+        };
+        assert_eq!(cleanup.smallest_directory(&arena).unwrap().path, "/a");
+
+        let enough = Cleanup {
+            capacity: 100_000_000,
+            required: 1,
+        };
+        assert_eq!(enough.minimal_set(&arena).unwrap(), Some(Vec::new()));
+        let impossible = Cleanup {
+            capacity: 100,
+            required: 100_000_000,
+        };
+        assert_eq!(impossible.smallest_directory(&arena), None);
+        assert_eq!(impossible.minimal_set(&arena).unwrap(), None);
+    }
+
+    #[test]
+    fn test_minimal_set_combines_directories() {
+        let arena = arena(concat!(
+            "$ ls\n",
+            "dir x\n",
+            "dir y\n",
+            "dir z\n",
+            "$ cd x\n",
+            "$ ls\n",
+            "dir w\n",
+            "20 f\n",
+            "$ cd w\n",
+            "$ ls\n",
+            "40 f\n",
+            "$ cd /y\n",
+            "$ ls\n",
+            "50 f\n",
+            "$ cd /z\n",
+            "$ ls\n",
+            "100 f\n",
+        ))
+        .unwrap();
+        let cleanup = Cleanup {
+            capacity: 210,
+            required: 105,
+        };
+        assert_eq!(cleanup.smallest_directory(&arena).unwrap().path, "/");
+        let paths = |cleanup: Cleanup| {
+            cleanup
+                .minimal_set(&arena)
+                .unwrap()
+                .unwrap()
+                .into_iter()
+                .map(|deletion| deletion.path)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(paths(cleanup), vec!["/x", "/y"]);
+        let cleanup = Cleanup {
+            capacity: 210,
+            required: 90,
+        };
+        assert_eq!(paths(cleanup), vec!["/x/w", "/y"]);
+    }
+
+    /// Directory tree with files of up to `max_size` bytes, like [`Arena::from_path`] reads.
+    fn random_fs_bytes(rng: &mut impl Rng, depth: usize, max_size: usize) -> FS {
+        let mut dir = HashMap::new();
+        for idx in 0..rng.gen_range(1..4) {
+            dir.insert(format!("f{}", idx), FS::File(rng.gen_range(0..max_size)));
+        }
+        if depth > 0 {
+            for idx in 0..rng.gen_range(0..4) {
+                dir.insert(
+                    format!("d{}", idx),
+                    random_fs_bytes(rng, depth - 1, max_size),
+                );
+            }
+        }
+        FS::Directory(dir)
+    }
+
+    #[test]
+    fn test_minimal_set_bytes() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(13);
+        let mut checked = 0;
+        while checked < 10 {
+            let arena = Arena::from_fs(&random_fs_bytes(&mut rng, 3, 1 << 20));
+            let dirs = arena.directories();
+            if dirs.len() > 14 {
+                continue;
+            }
+            checked += 1;
+            let paths: Vec<String> = dirs.iter().map(|dir| arena.path(dir.node)).collect();
+            let nested = |a: &str, b: &str| a == "/" || b.starts_with(&format!("{}/", a));
+            let cleanup = Cleanup {
+                capacity: arena.size(Arena::ROOT),
+                required: arena.size(Arena::ROOT) / rng.gen_range(2..8),
+            };
+            let to_free = cleanup.to_free(&arena);
+
+            // every set of directories none of which is inside another
+            let best = (0..1usize << dirs.len())
+                .filter(|set| {
+                    let chosen: Vec<usize> = (0..dirs.len())
+                        .filter(|idx| set & (1 << idx) != 0)
+                        .collect();
+                    chosen.iter().all(|a| {
+                        chosen
+                            .iter()
+                            .all(|b| a == b || !nested(&paths[*a], &paths[*b]))
+                    })
+                })
+                .map(|set| {
+                    (0..dirs.len())
+                        .filter(|idx| set & (1 << idx) != 0)
+                        .map(|idx| arena.size(dirs[idx].node))
+                        .sum::<usize>()
+                })
+                .filter(|total| *total >= to_free)
+                .min()
+                .unwrap();
+
+            let deletions = cleanup.minimal_set(&arena).unwrap().unwrap();
+            let freed: usize = deletions.iter().map(|deletion| deletion.size).sum();
+            assert_eq!(freed, best);
+            for a in &deletions {
+                assert_eq!(arena.du(&a.path), Some(a.size));
+                for b in &deletions {
+                    assert!(a == b || !nested(&a.path, &b.path));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_minimal_set_large_tree() {
+        let tree = |rng: &mut StdRng, max_size| {
+            let mut root = HashMap::new();
+            for idx in 0..150 {
+                root.insert(format!("d{}", idx), random_fs_bytes(rng, 3, max_size));
+            }
+            Arena::from_fs(&FS::Directory(root))
+        };
+        let quarter = |arena: &Arena| Cleanup {
+            capacity: arena.size(Arena::ROOT),
+            required: arena.size(Arena::ROOT) / 4,
+        };
+        let mut rng = StdRng::seed_from_u64(13);
+
+        // more than a thousand directories
+        let arena = tree(&mut rng, 1 << 12);
+        assert!(arena.directories().len() > 1000);
+        let cleanup = quarter(&arena);
+        let deletions = cleanup.minimal_set(&arena).unwrap().unwrap();
+        let freed: usize = deletions.iter().map(|deletion| deletion.size).sum();
+        assert!(freed >= cleanup.to_free(&arena));
+        assert!(freed < cleanup.smallest_directory(&arena).unwrap().size);
+        for a in &deletions {
+            assert_eq!(arena.du(&a.path), Some(a.size));
+            assert!(deletions
+                .iter()
+                .all(|b| !b.path.starts_with(&format!("{}/", a.path))));
+        }
+
+        // a few TB take too much memory rather than aborting
+        let arena = tree(&mut rng, 1 << 30);
+        let error = quarter(&arena).minimal_set(&arena).unwrap_err();
+        assert!(error.to_string().contains("512 MiB"), "{}", error);
+    }
+
+    #[test]
+    fn test_to_transcript() {
+        let transcript = sample_arena().to_transcript();
+        assert!(transcript.starts_with(concat!(
+            "$ cd /\n",
+            "$ ls\n",
+            "dir a\n",
+            "14848514 b.txt\n",
+            "8504156 c.dat\n",
+            "dir d\n",
+            "$ cd a\n",
+            "$ ls\n",
+            "dir e\n",
+            "29116 f\n",
+            "2557 g\n",
+            "62596 h.lst\n",
+            "$ cd e\n",
+            "$ ls\n",
+            "584 i\n",
+            "$ cd ..\n",
+            "$ cd ..\n",
+            "$ cd d\n",
+        )));
+        assert_eq!(Arena::default().to_transcript(), "$ cd /\n$ ls\n");
+    }
+
+    fn random_fs(rng: &mut impl Rng, depth: usize) -> FS {
+        const NAMES: [&str; 6] = ["a", "b.txt", "c-d", "e_f", "...", "0"];
+        let mut dir = HashMap::new();
+        for _ in 0..rng.gen_range(0..5) {
+            let name = NAMES[rng.gen_range(0..NAMES.len())].to_owned();
+            let fs = if depth > 0 && rng.gen_bool(0.4) {
+                random_fs(rng, depth - 1)
+            } else {
+                FS::File(rng.gen_range(0..1000))
+            };
+            dir.insert(name, fs);
+        }
+        FS::Directory(dir)
+    }
+
+    fn assert_round_trip(arena: &Arena) {
+        let transcript = arena.to_transcript();
+        let validation = Arena::validate(&VecFromLines::<CLI>::parse(&transcript).unwrap());
+        assert!(validation.issues.is_empty(), "{}", validation);
+        assert_eq!(validation.arena.to_fs(), arena.to_fs(), "{}", transcript);
+        assert_eq!(validation.arena.to_transcript(), transcript);
+    }
+
+    #[test]
+    fn test_transcript_round_trip() {
+        assert_round_trip(&sample_arena());
+        assert_round_trip(&Arena::default());
+        let mut rng = rand::rngs::StdRng::seed_from_u64(13);
+        for _ in 0..300 {
+            assert_round_trip(&Arena::from_fs(&random_fs(&mut rng, 4)));
+        }
+
+        // transcripts with `cd` paths and repeated listings come back in the canonical form
+        let arena = arena(concat!(
+            "$ cd a/b\n",
+            "$ ls\n",
+            "1 x\n",
+            "$ cd /a\n",
+            "$ ls\n",
+            "dir b\n",
+            "$ ls\n",
+            "2 y\n",
+        ))
+        .unwrap();
+        assert_round_trip(&arena);
+    }
+
+    #[test]
+    fn test_from_path() {
+        let root = std::env::temp_dir().join(format!("aoc-day07-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("a/e")).unwrap();
+        std::fs::create_dir_all(root.join("d")).unwrap();
+        std::fs::write(root.join("b.txt"), "hello").unwrap();
+        std::fs::write(root.join("a/f"), [0; 100]).unwrap();
+        std::fs::write(root.join("a/e/i"), "").unwrap();
+
+        let arena = Arena::from_path(&root).unwrap();
+        assert_eq!(arena.du("/"), Some(105));
+        assert_eq!(arena.du("/a"), Some(100));
+        assert_eq!(arena.du("/a/e/i"), Some(0));
+        let d = arena.get("/d").unwrap();
+        assert!(arena.is_dir(d) && arena.children(d, SortBy::Name).is_empty());
+        assert_round_trip(&arena);
+
+        std::fs::write(root.join("d/no spaces"), "").unwrap();
+        let error = Arena::from_path(&root).unwrap_err();
+        std::fs::remove_dir_all(&root).unwrap();
+        assert!(error.to_string().contains("no spaces"), "{}", error);
+        assert!(Arena::from_path(&root).is_err());
+    }
+
+    /// Creates the entries of `fs` in the directory at `path`, files filled with zeros.
+    fn write_fs(path: &Path, fs: &FS) {
+        let FS::Directory(dir) = fs else {
+            panic!("Only directories can be written");
+        };
+        for (name, fs) in dir {
+            match fs {
+                FS::File(size) => std::fs::write(path.join(name), vec![0; *size]).unwrap(),
+                FS::Directory(_) => {
+                    std::fs::create_dir(path.join(name)).unwrap();
+                    write_fs(&path.join(name), fs);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_path_large() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(13);
+        let (fs, arena) = loop {
+            let fs = random_fs_bytes(&mut rng, 6, 1 << 10);
+            let arena = Arena::from_fs(&fs);
+            if arena.directories().len() >= 100 {
+                break (fs, arena);
+            }
+        };
+        let root = std::env::temp_dir().join(format!("aoc-day07-large-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&root).unwrap();
+        write_fs(&root, &fs);
+
+        let from_path = Arena::from_path(&root);
+        std::fs::remove_dir_all(&root).unwrap();
+        let from_path = from_path.unwrap();
+        assert_eq!(from_path.to_fs(), fs);
+        assert_eq!(from_path.du("/"), arena.du("/"));
+        assert_eq!(from_path.find("f0").len(), from_path.directories().len());
+        assert_round_trip(&from_path);
+    }
+
+    #[test]
+    fn test_size_walk() {
+        let mut sizes = Vec::new();
+        let total = sample_arena().to_fs().size_walk(&mut |path, fs, size| {
+            if fs.is_dir() {
+                sizes.push((path.to_owned(), size));
+            }
+        });
+        assert_eq!(total, 48381165);
+        sizes.sort();
+        assert_eq!(
+            sizes,
+            vec![
+                ("/".to_owned(), 48381165),
+                ("/a".to_owned(), 94853),
+                ("/a/e".to_owned(), 584),
+                ("/d".to_owned(), 24933642),
+            ]
+        );
+        assert_eq!(FS::File(7).size_walk(&mut |_, _, _| {}), 7);
+    }
+
+    #[test]
+    fn test_deep_tree() {
+        // a file and a subdirectory on every level, the innermost directory is empty
+        const DEPTH: usize = 100_000;
+        let transcript = "$ ls\ndir d\n1 f\n$ cd d\n".repeat(DEPTH) + "$ ls\n";
+        let lines = VecFromLines::<CLI>::parse(&transcript).unwrap();
+        let validation = Arena::validate(&lines);
+        assert!(validation.issues.is_empty(), "{}", validation);
+        let arena = validation.arena;
+        let fs = arena.to_fs();
+
+        // paths are only borrowed, anything storing all of them would need quadratic memory
+        let mut deepest = 0;
+        assert_eq!(
+            fs.size_walk(&mut |path, entry, _| {
+                if entry.is_dir() {
+                    deepest = deepest.max(path.len());
+                }
+            }),
+            DEPTH
+        );
+        assert_eq!(deepest, "/d".len() * DEPTH);
+        assert_eq!(arena.file_count(), DEPTH);
+        assert_eq!(arena.to_transcript().lines().count(), 5 * DEPTH + 2);
+        let cleanup = Cleanup {
+            capacity: DEPTH,
+            required: 10,
+        };
+        let deletion = cleanup.smallest_directory(&arena).unwrap();
+        assert_eq!(deletion.size, 10);
+        assert_eq!(deletion.path, "/d".repeat(DEPTH - 10));
+        assert_eq!(cleanup.minimal_set(&arena).unwrap(), Some(vec![deletion]));
+        let deepest = arena.nodes().last().unwrap();
+        assert_eq!(arena.path(deepest).len(), "/d".len() * DEPTH);
+        assert_eq!(
+            arena.resolve(deepest, &"../".repeat(DEPTH)),
+            Some(Arena::ROOT)
+        );
+        assert_eq!(Arena::from_fs(&fs).size(Arena::ROOT), DEPTH);
+        fs.dismantle();
+        assert_eq!(Day07::solve_part1(&lines), DEPTH * (DEPTH + 1) / 2);
+        assert_eq!(Day07::solve_part2(&lines), 0);
+    }
+
+    #[test]
+    fn test_sample() {
+        assert_eq!(solve_part1::<Day07>(SAMPLE), 95437);
+        assert_eq!(solve_part2::<Day07>(SAMPLE), 24933642);
+    }
+}
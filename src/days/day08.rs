@@ -0,0 +1,117 @@
+use aoc_helpers::prelude::*;
+
+use crate::grid::Grid;
+
+pub struct Day08;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Tree {
+    pub height: usize,
+    pub visible: bool,
+}
+
+impl TryFrom<char> for Tree {
+    type Error = anyhow::Error;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '0'..='9' => Ok(Self {
+                height: (value as u8 - b'0') as usize,
+                ..Default::default()
+            }),
+            _ => Err(anyhow::anyhow!("Can't parse: {:?}", value)),
+        }
+    }
+}
+
+pub fn mark_visible<'a>(mut iter: impl Iterator<Item = &'a mut Tree>) {
+    let mut highest = iter.next().unwrap().height;
+    for tree in iter {
+        if highest < tree.height {
+            tree.visible = true;
+            highest = tree.height;
+        }
+    }
+}
+
+pub fn count_distance<'a>(mut iter: impl Iterator<Item = &'a Tree>) -> usize {
+    let height = iter.next().unwrap().height;
+    let mut distance = 0;
+    for tree in iter {
+        distance += 1;
+        if tree.height >= height {
+            break;
+        }
+    }
+    distance
+}
+
+impl Problem for Day08 {
+    type Input = Grid<Tree>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn solve_part1(input: &<Self::Input as aoc_helpers::scaffold::Parse>::Parsed) -> Self::Part1 {
+        let mut forrest = input.clone();
+        let (rows, cols) = (forrest.rows(), forrest.cols());
+
+        // mark edges as visible
+        for t in forrest.row_mut(0) {
+            t.visible = true;
+        }
+        for t in forrest.row_mut(rows - 1) {
+            t.visible = true;
+        }
+        for t in forrest.column_mut(0) {
+            t.visible = true;
+        }
+        for t in forrest.column_mut(cols - 1) {
+            t.visible = true;
+        }
+
+        // solve
+        for row in forrest.iter_rows_mut() {
+            mark_visible(row.iter_mut());
+            mark_visible(row.iter_mut().rev());
+        }
+        for column in 1..cols {
+            mark_visible(forrest.column_mut(column));
+            mark_visible(forrest.column_mut(column).rev());
+        }
+
+        // count visible
+        forrest.iter().filter(|t| t.visible).count()
+    }
+
+    fn solve_part2(input: &<Self::Input as aoc_helpers::scaffold::Parse>::Parsed) -> Self::Part2 {
+        let (rows, cols) = (input.rows(), input.cols());
+        let mut best_scenic_score = 0;
+        for row in 1..rows {
+            for col in 1..cols {
+                let right = count_distance(input.row(row)[col..].iter());
+                let left = count_distance(input.row(row)[..=col].iter().rev());
+                let down = count_distance(input.column(col).skip(row));
+                let up = count_distance(input.column(col).rev().skip(rows - row - 1));
+                let scenic_score = right * left * down * up;
+                if best_scenic_score < scenic_score {
+                    best_scenic_score = scenic_score;
+                }
+            }
+        }
+        best_scenic_score
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_helpers::scaffold::{solve_part1, solve_part2};
+
+    const SAMPLE: &str = include_str!("../../inputs/samples/day08-sample.txt");
+
+    #[test]
+    fn test_sample() {
+        assert_eq!(solve_part1::<Day08>(SAMPLE), 21);
+        assert_eq!(solve_part2::<Day08>(SAMPLE), 8);
+    }
+}
@@ -0,0 +1,304 @@
+use std::collections::VecDeque;
+
+use aoc_helpers::{prelude::*, scaffold::Parse};
+use rematch::rematch;
+
+use crate::parse_error::{lines_with_offsets, Blocks, ParseError};
+
+pub struct Day11;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[rematch]
+pub enum Operation {
+    #[rematch(r"\+")]
+    Add,
+    #[rematch(r"\*")]
+    Multiply,
+}
+
+impl Operation {
+    pub fn perform(self, operand1: usize, operand2: usize) -> usize {
+        match self {
+            Operation::Add => operand1 + operand2,
+            Operation::Multiply => operand1 * operand2,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[rematch]
+pub enum Operand {
+    #[rematch(r"old")]
+    Old,
+    #[rematch(r"(\d+)")]
+    Const(usize),
+}
+
+impl Operand {
+    pub fn eval(self, old: usize) -> usize {
+        match self {
+            Operand::Old => old,
+            Operand::Const(v) => v,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Monkey {
+    pub idx: usize,
+    pub items: VecDeque<usize>,
+    pub operation: Operation,
+    pub operands: [Operand; 2],
+    pub test: usize,
+    pub true_target: usize,
+    pub false_target: usize,
+    pub inspections: usize,
+}
+
+impl Parse for Monkey {
+    type Parsed = Self;
+
+    fn parse(raw_input: &str) -> anyhow::Result<Self::Parsed> {
+        let error = |offset, message: String| ParseError::new(11, raw_input, offset, message);
+        let number = |offset, s: &str| {
+            s.parse::<usize>()
+                .map_err(|e| error(offset, format!("Invalid number {:?}: {}", s, e)))
+        };
+        let mut lines = lines_with_offsets(raw_input).filter(|(_, line)| !line.trim().is_empty());
+        // finds the next line, checks it and returns what's between `prefix` and `suffix`
+        let mut field = |prefix: &str, suffix: &str| {
+            let (offset, line) = lines.next().ok_or_else(|| {
+                error(
+                    raw_input.trim_end().len(),
+                    format!("Expected a line starting with {:?}", prefix.trim_start()),
+                )
+            })?;
+            let value = line.strip_prefix(prefix).ok_or_else(|| {
+                let matching = line
+                    .bytes()
+                    .zip(prefix.bytes())
+                    .take_while(|(a, b)| a == b)
+                    .count();
+                error(
+                    offset + matching,
+                    format!("Expected {:?}", prefix.trim_start()),
+                )
+            })?;
+            let value = value
+                .strip_suffix(suffix)
+                .ok_or_else(|| error(offset + line.len(), format!("Expected {:?}", suffix)))?;
+            Ok::<_, ParseError>((offset + prefix.len(), value))
+        };
+
+        let (offset, idx) = field("Monkey ", ":")?;
+        let idx = number(offset, idx)?;
+
+        let (mut offset, raw_items) = field("  Starting items: ", "")?;
+        let mut items = VecDeque::new();
+        for item in raw_items.split(", ") {
+            items.push_back(number(offset, item)?);
+            offset += item.len() + 2;
+        }
+
+        let (offset, raw_operation) = field("  Operation: new = ", "")?;
+        let tokens: Vec<(usize, &str)> = raw_operation
+            .split(' ')
+            .scan(offset, |token_offset, token| {
+                let start = *token_offset;
+                *token_offset += token.len() + 1;
+                Some((start, token))
+            })
+            .filter(|(_, token)| !token.is_empty())
+            .collect();
+        let (operand1, operation, operand2) = match tokens.as_slice() {
+            [operand1, operation, operand2] => (*operand1, *operation, *operand2),
+            [_, _, _, (offset, _), ..] => {
+                return Err(
+                    error(*offset, "Unexpected token after the operation".to_owned()).into(),
+                )
+            }
+            _ => {
+                return Err(error(
+                    offset + raw_operation.len(),
+                    "Expected an operation like `old * 19`".to_owned(),
+                )
+                .into())
+            }
+        };
+        let operand = |(offset, s): (usize, &str)| {
+            s.parse::<Operand>()
+                .map_err(|_| error(offset, "Expected `old` or a number".to_owned()))
+        };
+        let operands = [operand(operand1)?, operand(operand2)?];
+        let operation = operation
+            .1
+            .parse::<Operation>()
+            .map_err(|_| error(operation.0, "Expected `+` or `*`".to_owned()))?;
+
+        let (offset, test) = field("  Test: divisible by ", "")?;
+        let test = number(offset, test)?;
+        let (offset, true_target) = field("    If true: throw to monkey ", "")?;
+        let true_target = number(offset, true_target)?;
+        let (offset, false_target) = field("    If false: throw to monkey ", "")?;
+        let false_target = number(offset, false_target)?;
+
+        if let Some((offset, _)) = lines.next() {
+            return Err(error(offset, "Unexpected line after the monkey".to_owned()).into());
+        }
+
+        Ok(Self {
+            idx,
+            items,
+            operation,
+            operands,
+            test,
+            true_target,
+            false_target,
+            inspections: 0,
+        })
+    }
+}
+
+pub struct InspectionResult {
+    pub target_monkey: usize,
+    pub item: usize,
+}
+
+impl Monkey {
+    pub fn inspect(&mut self, div: usize, modulo: usize) -> Option<InspectionResult> {
+        if let Some(item) = self.items.pop_front() {
+            self.inspections += 1;
+            let new_item = self
+                .operation
+                .perform(self.operands[0].eval(item), self.operands[1].eval(item))
+                / div
+                % modulo;
+            Some(InspectionResult {
+                target_monkey: if new_item.is_multiple_of(self.test) {
+                    self.true_target
+                } else {
+                    self.false_target
+                },
+                item: new_item,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn perform_round(monkeys: &mut [Self], div: usize, modulo: usize) {
+        for idx in 0..monkeys.len() {
+            while let Some(InspectionResult {
+                target_monkey,
+                item,
+            }) = monkeys[idx].inspect(div, modulo)
+            {
+                monkeys[target_monkey].items.push_back(item);
+            }
+        }
+    }
+}
+
+impl Problem for Day11 {
+    type Input = Blocks<Monkey>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn solve_part1(input: &<Self::Input as aoc_helpers::scaffold::Parse>::Parsed) -> Self::Part1 {
+        let mut monkeys = input.clone();
+        let modulo = monkeys.iter().map(|m| m.test).product();
+        for _ in 0..20 {
+            Monkey::perform_round(&mut monkeys, 3, modulo);
+        }
+        let mut inspection_counts: Vec<usize> =
+            monkeys.into_iter().map(|m| m.inspections).collect();
+        inspection_counts.sort_unstable();
+        inspection_counts.reverse();
+        inspection_counts[0] * inspection_counts[1]
+    }
+
+    fn solve_part2(input: &<Self::Input as aoc_helpers::scaffold::Parse>::Parsed) -> Self::Part2 {
+        let mut monkeys = input.clone();
+        let modulo = monkeys.iter().map(|m| m.test).product();
+        for _ in 0..10000 {
+            Monkey::perform_round(&mut monkeys, 1, modulo);
+        }
+        let mut inspection_counts: Vec<usize> =
+            monkeys.into_iter().map(|m| m.inspections).collect();
+        inspection_counts.sort_unstable();
+        inspection_counts.reverse();
+        inspection_counts[0] * inspection_counts[1]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_helpers::scaffold::{solve_part1, solve_part2};
+
+    const SAMPLE: &str = include_str!("../../inputs/samples/day11-sample.txt");
+
+    #[test]
+    fn test_monkey_parsing() {
+        let monkeys = Blocks::<Monkey>::parse(SAMPLE).unwrap();
+        assert_eq!(
+            monkeys[0],
+            Monkey {
+                idx: 0,
+                items: vec![79, 98].into(),
+                operation: Operation::Multiply,
+                operands: [Operand::Old, Operand::Const(19)],
+                test: 23,
+                true_target: 2,
+                false_target: 3,
+                inspections: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_monkey_parsing_errors() {
+        let error = |raw_input: &str| {
+            Monkey::parse(raw_input)
+                .unwrap_err()
+                .downcast::<ParseError>()
+                .unwrap()
+        };
+        let monkey = |items: &str, operation: &str| {
+            format!(
+                concat!(
+                    "Monkey 0:\n",
+                    "  Starting items: {}\n",
+                    "  Operation: new = {}\n",
+                    "  Test: divisible by 23\n",
+                    "    If true: throw to monkey 2\n",
+                    "    If false: throw to monkey 3\n",
+                ),
+                items, operation
+            )
+        };
+        assert!(Monkey::parse(&monkey("79, 98", "old * 19")).is_ok());
+
+        let err = error(&monkey("79, x8", "old * 19"));
+        assert_eq!((err.line, err.column), (2, 23));
+        assert_eq!(err.snippet, "  Starting items: 79, x8");
+
+        let err = error(&monkey("79, 98", "old / 19"));
+        assert_eq!((err.line, err.column), (3, 24));
+
+        let err = error(&monkey("79, 98", "old *"));
+        assert_eq!((err.line, err.column), (3, 25));
+
+        let err = error(&monkey("79, 98", "old * 19").replace("If false", "If not"));
+        assert_eq!((err.line, err.column), (6, 8));
+
+        let err = error(&monkey("79, 98", "old * 19").replace("Monkey 0:", "Monkey 0"));
+        assert_eq!((err.line, err.column), (1, 9));
+    }
+
+    #[test]
+    fn test_sample() {
+        assert_eq!(solve_part1::<Day11>(SAMPLE), 10605);
+        assert_eq!(solve_part2::<Day11>(SAMPLE), 2713310158);
+    }
+}
@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+
+use aoc_helpers::{prelude::*, walk::Walker};
+
+use crate::grid::Grid;
+
+pub const START: char = 'S';
+pub const END: char = 'E';
+
+pub struct Day12;
+
+pub type Coords = (usize, usize);
+
+pub struct PathFinder<'a> {
+    pub steps: HashMap<Coords, usize>,
+    map: &'a Grid<char>,
+    target: char,
+    uphill: bool,
+}
+
+impl<'a> PathFinder<'a> {
+    pub fn new(map: &'a Grid<char>, target: char, uphill: bool) -> Self {
+        Self {
+            steps: Default::default(),
+            map,
+            target,
+            uphill,
+        }
+    }
+}
+
+pub struct NextStepGenerator<'a> {
+    map: &'a Grid<char>,
+    from: Coords,
+    steps: usize,
+    uphill: bool,
+}
+
+pub fn normalise(c: char) -> usize {
+    if c == START {
+        0
+    } else if c == END {
+        (b'z' - b'a') as usize
+    } else {
+        assert!(c.is_ascii_lowercase());
+        (c as u8 - b'a') as usize
+    }
+}
+
+impl<'a> walk::Generator<(usize, Coords)> for NextStepGenerator<'a> {
+    fn generate<F: FnMut((usize, Coords))>(&mut self, mut callback: F) {
+        let current = normalise(self.map[self.from]);
+        for target in self.map.neighbours4(self.from) {
+            let target_height = normalise(self.map[target]);
+            if (self.uphill && target_height <= current + 1)
+                || (!self.uphill && current <= target_height + 1)
+            {
+                callback((self.steps, target));
+            }
+        }
+    }
+}
+
+impl<'a> Walker<(usize, Coords)> for PathFinder<'a> {
+    type NextGenerator = NextStepGenerator<'a>;
+
+    type Result = usize;
+
+    fn visit(
+        &mut self,
+        (steps, (row, col)): &(usize, Coords),
+    ) -> walk::VisitDecision<Self::Result, Self::NextGenerator> {
+        if self.map[(*row, *col)] == self.target {
+            return walk::VisitDecision::Break(*steps);
+        }
+        if let Some(previous_steps) = self.steps.get(&(*row, *col)).copied() {
+            if previous_steps <= *steps {
+                return walk::VisitDecision::Continue;
+            }
+        }
+        self.steps.insert((*row, *col), *steps);
+        walk::VisitDecision::Next(NextStepGenerator {
+            map: self.map,
+            from: (*row, *col),
+            steps: *steps + 1,
+            uphill: self.uphill,
+        })
+    }
+}
+
+impl Problem for Day12 {
+    type Input = Grid<char>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn solve_part1(input: &<Self::Input as aoc_helpers::scaffold::Parse>::Parsed) -> Self::Part1 {
+        let start = input
+            .position(|c| *c == START)
+            .expect("There should be a start position");
+
+        let mut walker = PathFinder::new(input, END, true);
+        walk::walk_broad(&mut walker, (0, start)).expect("There should be a path")
+    }
+
+    fn solve_part2(input: &<Self::Input as aoc_helpers::scaffold::Parse>::Parsed) -> Self::Part2 {
+        let start = input
+            .position(|c| *c == END)
+            .expect("There should be a start position");
+
+        let mut walker = PathFinder::new(input, 'a', false);
+        walk::walk_broad(&mut walker, (0, start)).expect("There should be a path")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_helpers::scaffold::{solve_part1, solve_part2};
+
+    const SAMPLE: &str = include_str!("../../inputs/samples/day12-sample.txt");
+
+    #[test]
+    fn test_sample() {
+        assert_eq!(solve_part1::<Day12>(SAMPLE), 31);
+        assert_eq!(solve_part2::<Day12>(SAMPLE), 29);
+    }
+}
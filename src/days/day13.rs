@@ -0,0 +1,1141 @@
+use std::{
+    borrow::Borrow,
+    cmp::Ordering,
+    fmt::{Display, Write},
+    io::BufRead,
+};
+
+use aoc_helpers::{prelude::*, scaffold::Parse};
+use serde::{ser::SerializeSeq, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::parse_error::{Blocks, Lines, ParseError};
+
+pub struct Day13;
+
+/// Packet ordered as the puzzle specifies, so a number equals a list holding just that number.
+///
+/// `PartialEq`, `Eq` and `Ord` agree with [`Packet::compare`]: `[[2]]` and `[2]` are equal.
+/// Parsing, `Display` and dropping handle any nesting depth, the rest recurses. As dropping is
+/// custom, lists can't be moved out of a packet by destructuring it, use [`Packet::into_list`].
+///
+/// Packets are JSON arrays of non-negative integers and (de)serialize as such. Like parsing,
+/// deserializing requires a list at the top level, it goes through `TryFrom<&serde_json::Value>`.
+/// Serde recurses per list, so packets nested deeper than [`MAX_SERDE_DEPTH`] lists are refused
+/// both ways.
+#[derive(Clone, Debug)]
+pub enum Packet {
+    List(Vec<Packet>),
+    Num(usize),
+}
+
+/// Deepest nesting of lists [`Packet`] (de)serializes, as far as `serde_json` parses arrays.
+pub const MAX_SERDE_DEPTH: usize = 127;
+
+impl Packet {
+    pub fn compare(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Packet::List(a), Packet::List(b)) => {
+                let mut a_iter = a.iter();
+                let mut b_iter = b.iter();
+                loop {
+                    match (a_iter.next(), b_iter.next()) {
+                        (None, None) => return Ordering::Equal,
+                        (None, Some(_)) => return Ordering::Less,
+                        (Some(_), None) => return Ordering::Greater,
+                        (Some(a), Some(b)) => {
+                            let result = Self::compare(a, b);
+                            if result != Ordering::Equal {
+                                return result;
+                            }
+                        }
+                    }
+                }
+            }
+            (Packet::List(_), Packet::Num(b)) => {
+                Self::compare(self, &Packet::List(vec![Packet::Num(*b)]))
+            }
+            (Packet::Num(a), Packet::List(_)) => {
+                Self::compare(&Packet::List(vec![Packet::Num(*a)]), other)
+            }
+            (Packet::Num(a), Packet::Num(b)) => a.cmp(b),
+        }
+    }
+
+    /// Compares like [`Packet::compare`] while recording every step it takes.
+    pub fn explain(&self, other: &Self) -> Trace {
+        let mut steps = Vec::new();
+        let ordering = explain_into(self, other, 0, None, &mut steps);
+        Trace { steps, ordering }
+    }
+}
+
+/// One of the two packets being compared.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+impl Display for Side {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Side::Left => "left",
+            Side::Right => "right",
+        })
+    }
+}
+
+/// Why a comparison ended before reaching the end of both packets.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Decision {
+    /// The side had the smaller number.
+    Smaller(Side),
+    /// The side's list ended first.
+    RanOut(Side),
+}
+
+impl Decision {
+    pub fn ordering(self) -> Ordering {
+        match self {
+            Decision::Smaller(Side::Left) | Decision::RanOut(Side::Left) => Ordering::Less,
+            Decision::Smaller(Side::Right) | Decision::RanOut(Side::Right) => Ordering::Greater,
+        }
+    }
+}
+
+/// Single step of a comparison, `depth` is its nesting level in the rendered trace.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Step {
+    /// Two values are compared, `index` is their position in the enclosing lists.
+    Compare {
+        depth: usize,
+        index: Option<usize>,
+        left: Packet,
+        right: Packet,
+    },
+    /// The number on `side` is turned into a single-element list to compare with a list.
+    Promote {
+        depth: usize,
+        side: Side,
+        promoted: Packet,
+    },
+    Decide {
+        depth: usize,
+        decision: Decision,
+    },
+}
+
+/// Steps of [`Packet::explain`] and the resulting ordering.
+///
+/// Displays in the indented style of the puzzle text:
+///
+/// ```text
+/// - Compare [[1],[2,3,4]] vs [[1],4]
+///   - Compare [1] vs [1]
+///     - Compare 1 vs 1
+///   - Compare [2,3,4] vs 4
+///     - Mixed types; convert right to [4] and retry comparison
+///     - Compare [2,3,4] vs [4]
+///       - Compare 2 vs 4
+///         - Left side is smaller, so inputs are in the right order
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Trace {
+    pub steps: Vec<Step>,
+    pub ordering: Ordering,
+}
+
+fn explain_into(
+    left: &Packet,
+    right: &Packet,
+    depth: usize,
+    index: Option<usize>,
+    steps: &mut Vec<Step>,
+) -> Ordering {
+    steps.push(Step::Compare {
+        depth,
+        index,
+        left: left.clone(),
+        right: right.clone(),
+    });
+    let decide = |steps: &mut Vec<Step>, decision: Decision| {
+        steps.push(Step::Decide {
+            depth: depth + 1,
+            decision,
+        });
+        decision.ordering()
+    };
+    match (left, right) {
+        (Packet::List(a), Packet::List(b)) => {
+            for idx in 0.. {
+                match (a.get(idx), b.get(idx)) {
+                    (None, None) => break,
+                    (None, Some(_)) => return decide(steps, Decision::RanOut(Side::Left)),
+                    (Some(_), None) => return decide(steps, Decision::RanOut(Side::Right)),
+                    (Some(a), Some(b)) => {
+                        let result = explain_into(a, b, depth + 1, Some(idx), steps);
+                        if result != Ordering::Equal {
+                            return result;
+                        }
+                    }
+                }
+            }
+            Ordering::Equal
+        }
+        (Packet::Num(a), Packet::Num(b)) => match a.cmp(b) {
+            Ordering::Less => decide(steps, Decision::Smaller(Side::Left)),
+            Ordering::Greater => decide(steps, Decision::Smaller(Side::Right)),
+            Ordering::Equal => Ordering::Equal,
+        },
+        (Packet::List(_), Packet::Num(n)) | (Packet::Num(n), Packet::List(_)) => {
+            let side = if matches!(left, Packet::Num(_)) {
+                Side::Left
+            } else {
+                Side::Right
+            };
+            let promoted = Packet::List(vec![Packet::Num(*n)]);
+            steps.push(Step::Promote {
+                depth: depth + 1,
+                side,
+                promoted: promoted.clone(),
+            });
+            match side {
+                Side::Left => explain_into(&promoted, right, depth + 1, None, steps),
+                Side::Right => explain_into(left, &promoted, depth + 1, None, steps),
+            }
+        }
+    }
+}
+
+impl Display for Trace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for step in &self.steps {
+            let depth = match step {
+                Step::Compare { depth, .. }
+                | Step::Promote { depth, .. }
+                | Step::Decide { depth, .. } => *depth,
+            };
+            write!(f, "{}- ", "  ".repeat(depth))?;
+            match step {
+                Step::Compare { left, right, .. } => writeln!(f, "Compare {} vs {}", left, right)?,
+                Step::Promote { side, promoted, .. } => writeln!(
+                    f,
+                    "Mixed types; convert {} to {} and retry comparison",
+                    side, promoted
+                )?,
+                Step::Decide { decision, .. } => {
+                    let (side, what) = match decision {
+                        Decision::Smaller(side) => (side, "is smaller"),
+                        Decision::RanOut(side) => (side, "ran out of items"),
+                    };
+                    let side = match side {
+                        Side::Left => "Left side",
+                        Side::Right => "Right side",
+                    };
+                    let verdict = match decision.ordering() {
+                        Ordering::Less => "in",
+                        _ => "not in",
+                    };
+                    writeln!(
+                        f,
+                        "{} {}, so inputs are {} the right order",
+                        side, what, verdict
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Renders the traces of all pairs like the puzzle text, with a `== Pair N ==` header for each.
+pub fn explain_pairs(pairs: &[Vec<Packet>]) -> String {
+    pairs
+        .iter()
+        .enumerate()
+        .map(|(idx, packets)| {
+            format!(
+                "== Pair {} ==\n{}",
+                idx + 1,
+                Packet::explain(&packets[0], &packets[1])
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+impl PartialEq for Packet {
+    fn eq(&self, other: &Self) -> bool {
+        self.compare(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Packet {}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.compare(other)
+    }
+}
+
+/// What the parser accepts at the current position.
+#[derive(Clone, Copy)]
+enum Expected {
+    ValueOrClose,
+    Value,
+    CommaOrClose,
+}
+
+impl Expected {
+    fn message(self) -> &'static str {
+        match self {
+            Expected::ValueOrClose => "Expected a number, `[` or `]`",
+            Expected::Value => "Expected a number or `[`",
+            Expected::CommaOrClose => "Expected `,` or `]`",
+        }
+    }
+}
+
+impl Parse for Packet {
+    type Parsed = Self;
+
+    /// Parses exactly the text `Display` produces, surrounded by optional whitespace.
+    ///
+    /// Open lists are kept on an explicit stack, so any nesting depth is fine.
+    fn parse(raw_input: &str) -> anyhow::Result<Self::Parsed> {
+        let error = |offset, message: &str| ParseError::new(13, raw_input, offset, message);
+        let start = raw_input.len() - raw_input.trim_start().len();
+        let end = raw_input.trim_end().len();
+        let bytes = &raw_input.as_bytes()[..end];
+        if bytes.get(start) != Some(&b'[') {
+            return Err(error(start, "Expected a packet starting with `[`").into());
+        }
+
+        let mut stack: Vec<Vec<Packet>> = Vec::new();
+        let mut expected = Expected::Value;
+        let mut offset = start;
+        loop {
+            let packet = match (bytes.get(offset), expected) {
+                (Some(b'['), Expected::ValueOrClose | Expected::Value) => {
+                    stack.push(Vec::new());
+                    expected = Expected::ValueOrClose;
+                    offset += 1;
+                    continue;
+                }
+                (Some(b']'), Expected::ValueOrClose | Expected::CommaOrClose) => {
+                    offset += 1;
+                    Packet::List(stack.pop().expect("There should be an open list"))
+                }
+                (Some(b','), Expected::CommaOrClose) => {
+                    expected = Expected::Value;
+                    offset += 1;
+                    continue;
+                }
+                (Some(b'0'..=b'9'), Expected::ValueOrClose | Expected::Value) => {
+                    let digits = bytes[offset..]
+                        .iter()
+                        .take_while(|b| b.is_ascii_digit())
+                        .count();
+                    let number = &raw_input[offset..offset + digits];
+                    if digits > 1 && number.starts_with('0') {
+                        return Err(error(offset, "Numbers can't have leading zeros").into());
+                    }
+                    let number = number
+                        .parse()
+                        .map_err(|e| error(offset, &format!("Invalid number: {}", e)))?;
+                    offset += digits;
+                    Packet::Num(number)
+                }
+                _ => return Err(error(offset, expected.message()).into()),
+            };
+            match stack.last_mut() {
+                Some(list) => {
+                    list.push(packet);
+                    expected = Expected::CommaOrClose;
+                }
+                None if offset == end => return Ok(packet),
+                None => return Err(error(offset, "Unexpected text after the packet").into()),
+            }
+        }
+    }
+}
+
+impl Display for Packet {
+    /// Writes lists with an explicit stack, so any nesting depth is fine.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let list = match self {
+            Packet::List(list) => list,
+            Packet::Num(n) => return n.fmt(f),
+        };
+        f.write_char('[')?;
+        let mut stack = vec![list.iter()];
+        let mut first = true;
+        while let Some(iter) = stack.last_mut() {
+            match iter.next() {
+                None => {
+                    f.write_char(']')?;
+                    stack.pop();
+                    first = false;
+                }
+                Some(packet) => {
+                    if !first {
+                        f.write_char(',')?;
+                    }
+                    match packet {
+                        Packet::List(list) => {
+                            f.write_char('[')?;
+                            stack.push(list.iter());
+                            first = true;
+                        }
+                        Packet::Num(n) => {
+                            n.fmt(f)?;
+                            first = false;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Packet {
+    /// Flattens nested lists before they're dropped, so deep packets don't overflow the stack.
+    fn drop(&mut self) {
+        if let Packet::List(list) = self {
+            let mut stack = std::mem::take(list);
+            while let Some(mut packet) = stack.pop() {
+                if let Packet::List(list) = &mut packet {
+                    stack.append(list);
+                }
+            }
+        }
+    }
+}
+
+/// Part of a JSON value that can't be represented in a packet.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Unrepresentable {
+    /// JSON Pointer to the value, e.g. `/1/0` (empty for the whole value).
+    pub path: String,
+    pub reason: &'static str,
+}
+
+/// All the parts of a JSON value that kept it from being converted into a [`Packet`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FromJsonError(pub Vec<Unrepresentable>);
+
+impl Display for FromJsonError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Value isn't a packet:")?;
+        for (idx, unrepresentable) in self.0.iter().enumerate() {
+            let separator = if idx == 0 { " " } else { ", " };
+            let path = match unrepresentable.path.as_str() {
+                "" => "the value",
+                path => path,
+            };
+            write!(f, "{}{} {}", separator, path, unrepresentable.reason)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for FromJsonError {}
+
+fn packet_from_json(
+    value: &serde_json::Value,
+    path: &mut String,
+    errors: &mut Vec<Unrepresentable>,
+) -> Option<Packet> {
+    use serde_json::Value;
+
+    let reason = match value {
+        Value::Array(values) => {
+            let mut list = Vec::with_capacity(values.len());
+            for (idx, value) in values.iter().enumerate() {
+                let len = path.len();
+                write!(path, "/{}", idx).unwrap();
+                list.extend(packet_from_json(value, path, errors));
+                path.truncate(len);
+            }
+            return Some(Packet::List(list));
+        }
+        Value::Number(n) => match (n.as_u64(), n.as_i64()) {
+            (Some(n), _) => match usize::try_from(n) {
+                Ok(n) => return Some(Packet::Num(n)),
+                Err(_) => "is too large",
+            },
+            (None, Some(_)) => "is a negative number",
+            (None, None) => "is a float",
+        },
+        Value::Null => "is null",
+        Value::Bool(_) => "is a boolean",
+        Value::String(_) => "is a string",
+        Value::Object(_) => "is an object",
+    };
+    errors.push(Unrepresentable {
+        path: path.clone(),
+        reason,
+    });
+    None
+}
+
+impl Packet {
+    /// Number of lists the deepest number (or empty list) is in, 0 for a number.
+    pub fn depth(&self) -> usize {
+        let mut deepest = 0;
+        let mut stack = vec![(self, 0)];
+        while let Some((packet, depth)) = stack.pop() {
+            match packet {
+                Packet::Num(_) => deepest = deepest.max(depth),
+                Packet::List(list) => {
+                    deepest = deepest.max(depth + 1);
+                    stack.extend(list.iter().map(|packet| (packet, depth + 1)));
+                }
+            }
+        }
+        deepest
+    }
+
+    /// The entries of the packet, a number being a list holding just that number.
+    pub fn into_list(mut self) -> Vec<Packet> {
+        match &mut self {
+            Packet::List(list) => std::mem::take(list),
+            Packet::Num(n) => vec![Packet::Num(*n)],
+        }
+    }
+}
+
+/// Serializes a packet already checked against [`MAX_SERDE_DEPTH`].
+struct Checked<'a>(&'a Packet);
+
+impl Serialize for Checked<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0 {
+            Packet::Num(num) => serializer.serialize_u64(*num as u64),
+            Packet::List(list) => {
+                let mut seq = serializer.serialize_seq(Some(list.len()))?;
+                for packet in list {
+                    seq.serialize_element(&Checked(packet))?;
+                }
+                seq.end()
+            }
+        }
+    }
+}
+
+impl Serialize for Packet {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let depth = self.depth();
+        if depth > MAX_SERDE_DEPTH {
+            return Err(serde::ser::Error::custom(format!(
+                "Packet nested {} lists deep, at most {} can be serialized",
+                depth, MAX_SERDE_DEPTH
+            )));
+        }
+        Checked(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Packet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        Packet::try_from(&value).map_err(serde::de::Error::custom)
+    }
+}
+
+impl TryFrom<&serde_json::Value> for Packet {
+    type Error = FromJsonError;
+
+    /// Converts a JSON array of non-negative integers and arrays, reporting every other sub-value.
+    fn try_from(value: &serde_json::Value) -> Result<Self, Self::Error> {
+        let mut errors = Vec::new();
+        if !value.is_array() {
+            errors.push(Unrepresentable {
+                path: String::new(),
+                reason: "isn't a list",
+            });
+            return Err(FromJsonError(errors));
+        }
+        match packet_from_json(value, &mut String::new(), &mut errors) {
+            Some(packet) if errors.is_empty() => Ok(packet),
+            _ => Err(FromJsonError(errors)),
+        }
+    }
+}
+
+/// `[[2]]` and `[[6]]`, the packets added in part 2.
+pub fn dividers() -> [Packet; 2] {
+    [2, 6].map(|n| Packet::List(vec![Packet::List(vec![Packet::Num(n)])]))
+}
+
+/// Iterator over the packets of a reader, one line at a time, see [`packets`].
+pub struct Packets<R> {
+    reader: R,
+    line: String,
+    line_number: usize,
+    /// Whether a blank line (or the start of input) came before the last packet.
+    after_blank: bool,
+}
+
+/// Reads packets lazily from `reader`, skipping blank lines.
+///
+/// Parse errors are [`ParseError`]s with line numbers counted in the whole input.
+pub fn packets<R: BufRead>(reader: R) -> Packets<R> {
+    Packets {
+        reader,
+        line: String::new(),
+        line_number: 0,
+        after_blank: true,
+    }
+}
+
+impl<R: BufRead> Packets<R> {
+    fn error(&self, message: &str) -> anyhow::Error {
+        let mut error = ParseError::new(13, &self.line, self.line.trim_end().len(), message);
+        error.line = self.line_number;
+        error.into()
+    }
+}
+
+impl<R: BufRead> Iterator for Packets<R> {
+    type Item = anyhow::Result<Packet>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut after_blank = false;
+        loop {
+            self.line.clear();
+            match self.reader.read_line(&mut self.line) {
+                Ok(0) => return None,
+                Ok(_) => self.line_number += 1,
+                Err(e) => return Some(Err(e.into())),
+            }
+            if self.line.trim().is_empty() {
+                after_blank = true;
+                continue;
+            }
+            self.after_blank |= after_blank;
+            return Some(Packet::parse(&self.line).map_err(|mut e| {
+                if let Some(parse_error) = e.downcast_mut::<ParseError>() {
+                    parse_error.line = self.line_number;
+                }
+                e
+            }));
+        }
+    }
+}
+
+/// Iterator over pairs of packets separated by blank lines, see [`pairs`].
+pub struct Pairs<R> {
+    packets: Packets<R>,
+}
+
+/// Reads pairs of packets lazily from `reader`, in the format of the puzzle input.
+pub fn pairs<R: BufRead>(reader: R) -> Pairs<R> {
+    Pairs {
+        packets: packets(reader),
+    }
+}
+
+impl<R: BufRead> Pairs<R> {
+    fn next_packet(&mut self) -> Option<anyhow::Result<(bool, Packet)>> {
+        self.packets.after_blank = false;
+        let packet = self.packets.next()?;
+        Some(packet.map(|packet| (self.packets.after_blank, packet)))
+    }
+}
+
+impl<R: BufRead> Iterator for Pairs<R> {
+    type Item = anyhow::Result<[Packet; 2]>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first_line = self.packets.line_number == 0;
+        let first = match self.next_packet()? {
+            Ok((after_blank, _)) if !after_blank && !first_line => {
+                return Some(Err(self
+                    .packets
+                    .error("Expected a blank line before the pair")))
+            }
+            Ok((_, packet)) => packet,
+            Err(e) => return Some(Err(e)),
+        };
+        let second = match self.next_packet() {
+            None => return Some(Err(self.packets.error("Expected a second packet"))),
+            Some(Ok((true, _))) => {
+                return Some(Err(self.packets.error(
+                    "Expected the second packet of the pair, not a blank line before it",
+                )))
+            }
+            Some(Ok((false, packet))) => packet,
+            Some(Err(e)) => return Some(Err(e)),
+        };
+        Some(Ok([first, second]))
+    }
+}
+
+/// Part 1 over a stream of pairs, keeping just one pair in memory.
+pub fn solve_part1_streaming(reader: impl BufRead) -> anyhow::Result<usize> {
+    let mut sum = 0;
+    for (idx, pair) in pairs(reader).enumerate() {
+        let [left, right] = pair?;
+        if left < right {
+            sum += idx + 1;
+        }
+    }
+    Ok(sum)
+}
+
+/// Part 2 over a stream of packets, keeping just one packet in memory.
+pub fn solve_part2_streaming(reader: impl BufRead) -> anyhow::Result<usize> {
+    let mut error = None;
+    let packets = packets(reader).map_while(|packet| packet.map_err(|e| error = Some(e)).ok());
+    let positions = divider_positions(packets, &dividers());
+    match error {
+        Some(e) => Err(e),
+        None => Ok(positions.iter().product()),
+    }
+}
+
+/// 1-based positions the `dividers` would have if they were added to `packets` and sorted.
+///
+/// Each position is one plus the number of packets and other dividers smaller than the divider,
+/// which takes `packets.len() * dividers.len()` comparisons instead of a sort. A divider goes
+/// before packets equal to it and after equal dividers earlier in `dividers`.
+pub fn divider_positions<P: Borrow<Packet>>(
+    packets: impl IntoIterator<Item = P>,
+    dividers: &[Packet],
+) -> Vec<usize> {
+    let mut positions: Vec<usize> = dividers
+        .iter()
+        .enumerate()
+        .map(|(idx, divider)| {
+            1 + dividers
+                .iter()
+                .enumerate()
+                .filter(|(other_idx, other)| {
+                    *other < divider || (*other == divider && *other_idx < idx)
+                })
+                .count()
+        })
+        .collect();
+    for packet in packets {
+        let packet = packet.borrow();
+        for (position, divider) in positions.iter_mut().zip(dividers) {
+            if packet < divider {
+                *position += 1;
+            }
+        }
+    }
+    positions
+}
+
+impl Problem for Day13 {
+    type Input = Blocks<Lines<Packet>>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn solve_part1(input: &<Self::Input as aoc_helpers::scaffold::Parse>::Parsed) -> Self::Part1 {
+        input
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, packets)| {
+                if packets[0] < packets[1] {
+                    Some(idx + 1)
+                } else {
+                    None
+                }
+            })
+            .sum()
+    }
+
+    fn solve_part2(input: &<Self::Input as aoc_helpers::scaffold::Parse>::Parsed) -> Self::Part2 {
+        divider_positions(input.iter().flatten(), &dividers())
+            .iter()
+            .product()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+    use aoc_helpers::scaffold::{solve_part1, solve_part2};
+    use rand::{Rng, SeedableRng};
+
+    const SAMPLE: &str = include_str!("../../inputs/samples/day13-sample.txt");
+
+    #[test]
+    fn test_sample() {
+        assert_eq!(solve_part1::<Day13>(SAMPLE), 13);
+        assert_eq!(solve_part2::<Day13>(SAMPLE), 140);
+    }
+
+    #[test]
+    fn test_compare_mixed() {
+        let compare = |a: &str, b: &str| {
+            Packet::compare(&Packet::parse(a).unwrap(), &Packet::parse(b).unwrap())
+        };
+        assert_eq!(compare("[[2]]", "[2]"), Ordering::Equal);
+        assert_eq!(compare("[2]", "[[2]]"), Ordering::Equal);
+        assert_eq!(compare("[[2],1]", "[2]"), Ordering::Greater);
+        assert_eq!(compare("[[]]", "[2]"), Ordering::Less);
+        assert_eq!(compare("[2,1]", "[[2,0]]"), Ordering::Less);
+    }
+
+    fn random_packet(rng: &mut impl Rng, depth: usize) -> Packet {
+        // small numbers and short lists so that equal packets come up often
+        if depth == 0 || rng.gen_bool(0.3) {
+            Packet::Num(rng.gen_range(0..3))
+        } else {
+            Packet::List(
+                (0..rng.gen_range(0..4))
+                    .map(|_| random_packet(rng, depth - 1))
+                    .collect(),
+            )
+        }
+    }
+
+    fn random_packets(count: usize) -> Vec<Packet> {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(13);
+        (0..count).map(|_| random_packet(&mut rng, 4)).collect()
+    }
+
+    #[test]
+    fn test_order_is_reflexive_and_antisymmetric() {
+        let packets = random_packets(200);
+        for a in &packets {
+            assert_eq!(a.cmp(a), Ordering::Equal, "{}", a);
+            assert_eq!(a, &a.clone());
+            for b in &packets {
+                assert_eq!(a.cmp(b), b.cmp(a).reverse(), "{} vs {}", a, b);
+                assert_eq!(a == b, a.cmp(b) == Ordering::Equal, "{} vs {}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_order_is_transitive() {
+        let packets = random_packets(120);
+        for a in &packets {
+            for b in packets.iter().filter(|b| a <= *b) {
+                for c in packets.iter().filter(|c| b <= *c) {
+                    assert!(a <= c, "{} <= {} <= {} but not {} <= {}", a, b, c, a, c);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_collections() {
+        let mut packets = random_packets(100);
+        packets.sort();
+        assert!(packets.windows(2).all(|w| w[0] <= w[1]));
+
+        let set: BTreeSet<Packet> = packets.iter().cloned().collect();
+        packets.dedup();
+        assert_eq!(set.len(), packets.len());
+        assert!(set.contains(&Packet::parse("[[2]]").unwrap()) == set.contains(&Packet::Num(2)));
+    }
+
+    #[test]
+    fn test_parsing_and_display() {
+        for l in SAMPLE.lines() {
+            if !l.is_empty() {
+                let packet = Packet::parse(l).unwrap();
+                assert_eq!(l, format!("{packet}"));
+            }
+        }
+    }
+
+    #[test]
+    fn test_parsing_errors() {
+        let error = |raw_input: &str| {
+            Packet::parse(raw_input)
+                .unwrap_err()
+                .downcast::<ParseError>()
+                .unwrap()
+        };
+        let err = error("[1,[2,99999999999999999999999]]");
+        assert_eq!((err.line, err.column), (1, 7));
+        let err = error("1,2]");
+        assert_eq!((err.line, err.column), (1, 1));
+        let err = error("[1,[2]");
+        assert_eq!((err.line, err.column), (1, 7));
+        assert!(error("").message.contains("`[`"));
+
+        for (raw_input, column) in [
+            ("[1,,2]", 4),
+            ("[1,]", 4),
+            ("[,1]", 2),
+            ("[1 ,2]", 3),
+            ("[1,2]]", 6),
+            ("[1,2] x", 6),
+            ("[1][2]", 4),
+            ("[a]", 2),
+            ("[01]", 2),
+            ("[[1]2]", 5),
+            ("[-1]", 2),
+        ] {
+            let err = error(raw_input);
+            assert_eq!((err.line, err.column), (1, column), "{:?}", raw_input);
+        }
+        assert!(Packet::parse(" [0,10]\r\n").is_ok());
+    }
+
+    #[test]
+    fn test_explain() {
+        let pairs = Blocks::<Lines<Packet>>::parse(SAMPLE).unwrap();
+        let rendered = explain_pairs(&pairs);
+        assert!(rendered.starts_with(concat!(
+            "== Pair 1 ==\n",
+            "- Compare [1,1,3,1,1] vs [1,1,5,1,1]\n",
+            "  - Compare 1 vs 1\n",
+            "  - Compare 1 vs 1\n",
+            "  - Compare 3 vs 5\n",
+            "    - Left side is smaller, so inputs are in the right order\n",
+            "\n",
+            "== Pair 2 ==\n",
+            "- Compare [[1],[2,3,4]] vs [[1],4]\n",
+            "  - Compare [1] vs [1]\n",
+            "    - Compare 1 vs 1\n",
+            "  - Compare [2,3,4] vs 4\n",
+            "    - Mixed types; convert right to [4] and retry comparison\n",
+            "    - Compare [2,3,4] vs [4]\n",
+            "      - Compare 2 vs 4\n",
+            "        - Left side is smaller, so inputs are in the right order\n",
+            "\n",
+            "== Pair 3 ==\n",
+            "- Compare [9] vs [[8,7,6]]\n",
+            "  - Compare 9 vs [8,7,6]\n",
+            "    - Mixed types; convert left to [9] and retry comparison\n",
+            "    - Compare [9] vs [8,7,6]\n",
+            "      - Compare 9 vs 8\n",
+            "        - Right side is smaller, so inputs are not in the right order\n",
+            "\n",
+            "== Pair 4 ==\n",
+            "- Compare [[4,4],4,4] vs [[4,4],4,4,4]\n",
+            "  - Compare [4,4] vs [4,4]\n",
+            "    - Compare 4 vs 4\n",
+            "    - Compare 4 vs 4\n",
+            "  - Compare 4 vs 4\n",
+            "  - Compare 4 vs 4\n",
+            "  - Left side ran out of items, so inputs are in the right order\n",
+        )));
+        assert!(rendered.contains(concat!(
+            "== Pair 6 ==\n",
+            "- Compare [] vs [3]\n",
+            "  - Left side ran out of items, so inputs are in the right order\n",
+        )));
+
+        let trace = Packet::explain(&pairs[4][0], &pairs[4][1]);
+        assert_eq!(trace.ordering, Ordering::Greater);
+        assert_eq!(
+            trace.steps.last(),
+            Some(&Step::Decide {
+                depth: 1,
+                decision: Decision::RanOut(Side::Right)
+            })
+        );
+        assert!(matches!(
+            trace.steps[3],
+            Step::Compare { index: Some(2), .. }
+        ));
+    }
+
+    #[test]
+    fn test_explain_agrees_with_compare() {
+        let packets = random_packets(60);
+        for a in &packets {
+            for b in &packets {
+                assert_eq!(a.explain(b).ordering, a.cmp(b), "{} vs {}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_streaming() {
+        assert_eq!(solve_part1_streaming(SAMPLE.as_bytes()).unwrap(), 13);
+        assert_eq!(solve_part2_streaming(SAMPLE.as_bytes()).unwrap(), 140);
+        assert_eq!(packets(SAMPLE.as_bytes()).count(), 16);
+
+        let input = include_str!("../../inputs/day13.txt");
+        assert_eq!(
+            solve_part1_streaming(input.as_bytes()).unwrap(),
+            solve_part1::<Day13>(input)
+        );
+        assert_eq!(
+            solve_part2_streaming(input.as_bytes()).unwrap(),
+            solve_part2::<Day13>(input)
+        );
+
+        let from_pairs: Vec<String> = pairs(SAMPLE.as_bytes())
+            .flat_map(|pair| pair.unwrap())
+            .map(|packet| packet.to_string())
+            .collect();
+        let lines: Vec<&str> = SAMPLE.lines().filter(|line| !line.is_empty()).collect();
+        assert_eq!(from_pairs, lines);
+
+        assert_eq!(
+            pairs("[1]\r\n[2]\r\n\r\n\r\n[3]\r\n[4]".as_bytes())
+                .map(|pair| pair.unwrap().map(|packet| packet.to_string()))
+                .collect::<Vec<_>>(),
+            vec![["[1]", "[2]"], ["[3]", "[4]"]]
+        );
+    }
+
+    #[test]
+    fn test_streaming_errors() {
+        fn error<T>(mut iter: impl Iterator<Item = anyhow::Result<T>>) -> ParseError {
+            iter.find_map(Result::err)
+                .unwrap()
+                .downcast::<ParseError>()
+                .unwrap()
+        }
+        let err = error(packets("[1]\n\n[2]\n[3,,]\n".as_bytes()));
+        assert_eq!((err.line, err.column), (4, 4));
+        assert_eq!(err.snippet, "[3,,]");
+
+        let err = error(pairs("[1]\n[2]\n\n[3]\n".as_bytes()));
+        assert_eq!(err.line, 4);
+        let err = error(pairs("[1]\n[2]\n\n[3]\n\n[4]\n".as_bytes()));
+        assert_eq!(err.line, 6);
+        let err = error(pairs("[1]\n[2]\n[3]\n[4]\n".as_bytes()));
+        assert_eq!(err.line, 3);
+    }
+
+    #[test]
+    fn test_divider_positions() {
+        let packets = random_packets(300);
+        let mut dividers = random_packets(310).split_off(300);
+        dividers.push(dividers[0].clone());
+        let positions = divider_positions(&packets, &dividers);
+
+        // after sorting, a divider is at the first element equal to it, shifted by the equal
+        // dividers listed before it
+        let mut sorted: Vec<&Packet> = packets.iter().chain(&dividers).collect();
+        sorted.sort();
+        for (idx, divider) in dividers.iter().enumerate() {
+            let first_equal = sorted.iter().position(|p| *p == divider).unwrap();
+            let equal_dividers_before = dividers[..idx].iter().filter(|d| *d == divider).count();
+            assert_eq!(positions[idx], first_equal + 1 + equal_dividers_before);
+        }
+
+        assert_eq!(
+            divider_positions(Vec::<Packet>::new(), &super::dividers()),
+            vec![1, 2]
+        );
+    }
+
+    #[test]
+    fn test_serde() {
+        for line in SAMPLE.lines().filter(|line| !line.is_empty()) {
+            let packet = Packet::parse(line).unwrap();
+            assert_eq!(serde_json::to_string(&packet).unwrap(), line);
+
+            let deserialized: Packet = serde_json::from_str(line).unwrap();
+            assert_eq!(deserialized.to_string(), line);
+
+            let value: serde_json::Value = serde_json::from_str(line).unwrap();
+            assert_eq!(Packet::try_from(&value).unwrap().to_string(), line);
+        }
+        assert!(serde_json::from_str::<Packet>("[1,-2]").is_err());
+        assert_eq!(
+            serde_json::from_str::<Packet>("7").unwrap_err().to_string(),
+            "Value isn't a packet: the value isn't a list"
+        );
+        assert!(serde_json::from_str::<Packet>("[[1],\"x\"]").is_err());
+    }
+
+    #[test]
+    fn test_serde_depth() {
+        let nested = |depth| "[".repeat(depth) + "1" + &"]".repeat(depth);
+        assert_eq!(Packet::Num(1).depth(), 0);
+        assert_eq!(Packet::parse("[[],[[1]]]").unwrap().depth(), 3);
+
+        let deepest = Packet::parse(&nested(MAX_SERDE_DEPTH)).unwrap();
+        assert_eq!(deepest.depth(), MAX_SERDE_DEPTH);
+        let json = serde_json::to_string(&deepest).unwrap();
+        assert_eq!(json, nested(MAX_SERDE_DEPTH));
+        assert_eq!(serde_json::from_str::<Packet>(&json).unwrap(), deepest);
+
+        let too_deep = Packet::parse(&nested(MAX_SERDE_DEPTH + 1)).unwrap();
+        assert!(serde_json::to_string(&too_deep).is_err());
+        assert!(serde_json::from_str::<Packet>(&nested(MAX_SERDE_DEPTH + 1)).is_err());
+
+        // refused instead of overflowing the stack
+        let very_deep = Packet::parse(&nested(100_000)).unwrap();
+        assert_eq!(
+            serde_json::to_string(&very_deep).unwrap_err().to_string(),
+            "Packet nested 100000 lists deep, at most 127 can be serialized"
+        );
+        assert!(serde_json::from_str::<Packet>(&nested(100_000)).is_err());
+    }
+
+    #[test]
+    fn test_from_json_errors() {
+        let value = serde_json::json!([1, "a", [2.5, [-3, {"x": 1}]], null, [true]]);
+        let err = Packet::try_from(&value).unwrap_err();
+        assert_eq!(
+            err.0
+                .iter()
+                .map(|u| (u.path.as_str(), u.reason))
+                .collect::<Vec<_>>(),
+            vec![
+                ("/1", "is a string"),
+                ("/2/0", "is a float"),
+                ("/2/1/0", "is a negative number"),
+                ("/2/1/1", "is an object"),
+                ("/3", "is null"),
+                ("/4/0", "is a boolean"),
+            ]
+        );
+        assert_eq!(
+            Packet::try_from(&serde_json::json!(7))
+                .unwrap_err()
+                .to_string(),
+            "Value isn't a packet: the value isn't a list"
+        );
+        assert_eq!(
+            Packet::try_from(&serde_json::json!([[], "x"]))
+                .unwrap_err()
+                .to_string(),
+            "Value isn't a packet: /1 is a string"
+        );
+    }
+
+    #[test]
+    fn test_into_list() {
+        let packet = Packet::parse("[[1],2,[]]").unwrap();
+        let list = packet.into_list();
+        assert_eq!(list.len(), 3);
+        assert_eq!(list[0].to_string(), "[1]");
+        assert_eq!(list[2].to_string(), "[]");
+        assert_eq!(
+            list.into_iter()
+                .map(|packet| packet.into_list().len())
+                .collect::<Vec<_>>(),
+            [1, 1, 0]
+        );
+    }
+
+    #[test]
+    fn test_deep_nesting() {
+        let depth = 100_000;
+        let raw_input = format!("{}7{}", "[".repeat(depth), "]".repeat(depth));
+        let packet = Packet::parse(&raw_input).unwrap();
+        assert_eq!(packet.to_string(), raw_input);
+
+        let err = Packet::parse(&raw_input[..raw_input.len() - 1])
+            .unwrap_err()
+            .downcast::<ParseError>()
+            .unwrap();
+        assert_eq!(err.column, raw_input.len());
+    }
+}
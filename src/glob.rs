@@ -0,0 +1,81 @@
+/// Whether `text` matches a shell-like glob `pattern`, as a path: `*` and `?` match any text
+/// and any character except `/`, `**` matches anything and every other character itself.
+pub fn matches(pattern: &str, text: &str) -> bool {
+    let text = text.as_bytes();
+    // `reached[end]`: whether the pattern so far matches `text[..end]`, which takes time
+    // proportional to the length of the pattern times the length of the text whatever the stars
+    let mut reached = vec![false; text.len() + 1];
+    reached[0] = true;
+    let mut pattern = pattern.as_bytes();
+    loop {
+        let mut next = vec![false; text.len() + 1];
+        pattern = match pattern {
+            [] => break,
+            [b'*', b'*', rest @ ..] => {
+                let mut any = false;
+                for (end, next) in next.iter_mut().enumerate() {
+                    any |= reached[end];
+                    *next = any;
+                }
+                rest
+            }
+            [b'*', rest @ ..] => {
+                next[0] = reached[0];
+                for end in 1..=text.len() {
+                    next[end] = reached[end] || (next[end - 1] && text[end - 1] != b'/');
+                }
+                rest
+            }
+            [c, rest @ ..] => {
+                for end in 1..=text.len() {
+                    let t = text[end - 1];
+                    let fits = if *c == b'?' { t != b'/' } else { t == *c };
+                    next[end] = reached[end - 1] && fits;
+                }
+                rest
+            }
+        };
+        reached = next;
+    }
+    reached[text.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches() {
+        assert!(matches("", ""));
+        assert!(matches("a.txt", "a.txt"));
+        assert!(!matches("a.txt", "a.txt2"));
+        assert!(matches("*.txt", "a.txt"));
+        assert!(matches("*", ""));
+        assert!(!matches("*", "a/b"));
+        assert!(matches("*/*", "a/b"));
+        assert!(matches("**", "a/b/c"));
+        assert!(matches("a/**/c", "a/b/b/c"));
+        assert!(matches("?.rs", "a.rs"));
+        assert!(!matches("?", "/"));
+        assert!(!matches("?", ""));
+        assert!(matches("a*", "a"));
+        assert!(!matches("a*b", "a/b"));
+        assert!(matches("a**b", "a/b"));
+        assert!(matches("***", "a/b"));
+        assert!(matches("**/*.rs", "src/days/day07.rs"));
+        assert!(!matches("**/*.rs", "day07.rs"));
+        assert!(matches("*?", "ab"));
+        assert!(!matches("a?", "a"));
+    }
+
+    #[test]
+    fn test_matches_pathological() {
+        let text = "a".repeat(10_000);
+        assert!(!matches("*a*a*a*a*a*a*a*a*b", &text));
+        assert!(matches("*a*a*a*a*a*a*a*a*a", &text));
+        let path = ["a"; 2_000].join("/");
+        assert!(!matches("**a**a**a**a**a**a**b", &path));
+        assert!(!matches("*/*/*/*/*/*/*/b", &path));
+        assert!(matches("**a/a/*", &path));
+    }
+}
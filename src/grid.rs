@@ -0,0 +1,465 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use aoc_helpers::scaffold::Parse;
+
+use crate::parse_error::{lines_with_offsets, ParseError};
+
+/// Position in a [`Grid`] as `(row, col)`, `(0, 0)` being the top left corner.
+pub type Pos = (usize, usize);
+
+/// Offsets of the 4-neighbourhood in reading order.
+const NEIGHBOURS_4: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+/// Offsets of the 8-neighbourhood in reading order.
+const NEIGHBOURS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// Rectangular grid stored row by row in a single `Vec`.
+///
+/// Cells are indexed by [`Pos`], `grid[(row, col)]`. Parsing takes one row per non-empty line
+/// and one cell per character (converted with `TryFrom<char>`), all rows have to be equally long.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(rows: usize, cols: usize, cell: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: vec![cell; rows * cols],
+            rows,
+            cols,
+        }
+    }
+
+    /// `None` if the rows aren't equally long.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let cols = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != cols) {
+            return None;
+        }
+        Some(Self {
+            rows: if cols == 0 { 0 } else { rows.len() },
+            cells: rows.into_iter().flatten().collect(),
+            cols,
+        })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn contains(&self, (row, col): Pos) -> bool {
+        row < self.rows && col < self.cols
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[pos.0 * self.cols + pos.1])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.cells[pos.0 * self.cols + pos.1])
+        } else {
+            None
+        }
+    }
+
+    /// All cells in reading order.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    /// All cells in reading order together with their positions.
+    pub fn positions(&self) -> impl Iterator<Item = (Pos, &T)> {
+        let cols = self.cols;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(idx, cell)| ((idx / cols, idx % cols), cell))
+    }
+
+    /// Position of the first cell (in reading order) matching `predicate`.
+    pub fn position(&self, predicate: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.cells
+            .iter()
+            .position(predicate)
+            .map(|idx| (idx / self.cols, idx % self.cols))
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        &mut self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    /// All rows from the top.
+    pub fn iter_rows(&self) -> impl DoubleEndedIterator<Item = &[T]> {
+        self.cells.chunks(self.cols.max(1))
+    }
+
+    pub fn iter_rows_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut [T]> {
+        self.cells.chunks_mut(self.cols.max(1))
+    }
+
+    /// Range of `cells` holding `count` cells `step` apart from `start`, with the cells in between.
+    fn strided(&self, start: usize, step: usize, count: usize) -> (std::ops::Range<usize>, usize) {
+        let range = match count {
+            0 => 0..0,
+            _ => start..start + (count - 1) * step + 1,
+        };
+        (range, step.max(1))
+    }
+
+    fn column_range(&self, col: usize) -> (std::ops::Range<usize>, usize) {
+        assert!(col < self.cols, "Column {} out of {}", col, self.cols);
+        self.strided(col, self.cols, self.rows)
+    }
+
+    fn diagonal_range(&self, (row, col): Pos) -> (std::ops::Range<usize>, usize) {
+        let count = if self.contains((row, col)) {
+            (self.rows - row).min(self.cols - col)
+        } else {
+            0
+        };
+        self.strided(row * self.cols + col, self.cols + 1, count)
+    }
+
+    fn anti_diagonal_range(&self, (row, col): Pos) -> (std::ops::Range<usize>, usize) {
+        let count = if self.contains((row, col)) {
+            (self.rows - row).min(col + 1)
+        } else {
+            0
+        };
+        self.strided(row * self.cols + col, self.cols.saturating_sub(1), count)
+    }
+
+    /// Cells of a column from the top.
+    pub fn column(&self, col: usize) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        let (range, step) = self.column_range(col);
+        self.cells[range].iter().step_by(step)
+    }
+
+    pub fn column_mut(
+        &mut self,
+        col: usize,
+    ) -> impl DoubleEndedIterator<Item = &mut T> + ExactSizeIterator {
+        let (range, step) = self.column_range(col);
+        self.cells[range].iter_mut().step_by(step)
+    }
+
+    /// Cells from `pos` down and to the right up to the edge, empty if `pos` is outside.
+    pub fn diagonal(&self, pos: Pos) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        let (range, step) = self.diagonal_range(pos);
+        self.cells[range].iter().step_by(step)
+    }
+
+    pub fn diagonal_mut(
+        &mut self,
+        pos: Pos,
+    ) -> impl DoubleEndedIterator<Item = &mut T> + ExactSizeIterator {
+        let (range, step) = self.diagonal_range(pos);
+        self.cells[range].iter_mut().step_by(step)
+    }
+
+    /// Cells from `pos` down and to the left up to the edge, empty if `pos` is outside.
+    pub fn anti_diagonal(
+        &self,
+        pos: Pos,
+    ) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        let (range, step) = self.anti_diagonal_range(pos);
+        self.cells[range].iter().step_by(step)
+    }
+
+    pub fn anti_diagonal_mut(
+        &mut self,
+        pos: Pos,
+    ) -> impl DoubleEndedIterator<Item = &mut T> + ExactSizeIterator {
+        let (range, step) = self.anti_diagonal_range(pos);
+        self.cells[range].iter_mut().step_by(step)
+    }
+
+    fn offsets(
+        &self,
+        (row, col): Pos,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = Pos> {
+        let (rows, cols) = (self.rows, self.cols);
+        offsets.iter().filter_map(move |(drow, dcol)| {
+            let pos = (
+                row.checked_add_signed(*drow)?,
+                col.checked_add_signed(*dcol)?,
+            );
+            (pos.0 < rows && pos.1 < cols).then_some(pos)
+        })
+    }
+
+    /// Positions of the cells above, left, right and below `pos` that are inside the grid.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> {
+        self.offsets(pos, &NEIGHBOURS_4)
+    }
+
+    /// Positions of the up to 8 cells around `pos`, in reading order.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> {
+        self.offsets(pos, &NEIGHBOURS_8)
+    }
+
+    pub fn map<U>(&self, fun: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(fun).collect(),
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        self.get(pos).unwrap_or_else(|| {
+            panic!(
+                "Position {:?} out of a {}x{} grid",
+                pos, self.rows, self.cols
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        let (rows, cols) = (self.rows, self.cols);
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("Position {:?} out of a {}x{} grid", pos, rows, cols))
+    }
+}
+
+impl<T> Parse for Grid<T>
+where
+    T: TryFrom<char>,
+    <T as TryFrom<char>>::Error: Display,
+{
+    type Parsed = Self;
+
+    fn parse(raw_input: &str) -> anyhow::Result<Self::Parsed> {
+        let error = |offset, message: String| ParseError::at(raw_input, offset, message);
+        let mut rows = Vec::new();
+        for (line_offset, line) in
+            lines_with_offsets(raw_input).filter(|(_, line)| !line.trim().is_empty())
+        {
+            let row = line
+                .char_indices()
+                .map(|(idx, c)| T::try_from(c).map_err(|e| error(line_offset + idx, e.to_string())))
+                .collect::<Result<Vec<T>, _>>()?;
+            if let Some(first) = rows.first().map(Vec::len) {
+                if row.len() != first {
+                    // point at the first cell too many or right after the last one
+                    let idx = line
+                        .char_indices()
+                        .nth(first)
+                        .map_or(line.len(), |(idx, _)| idx);
+                    return Err(error(
+                        line_offset + idx,
+                        format!(
+                            "Row has {} cells, expected {} like the first row",
+                            row.len(),
+                            first
+                        ),
+                    )
+                    .into());
+                }
+            }
+            rows.push(row);
+        }
+        Ok(Self::from_rows(rows).expect("Rows should be equally long"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits() -> Grid<u32> {
+        // 1 2 3 4
+        // 5 6 7 8
+        // 9 0 1 2
+        Grid::from_rows(vec![vec![1, 2, 3, 4], vec![5, 6, 7, 8], vec![9, 0, 1, 2]]).unwrap()
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Digit(u32);
+
+    impl TryFrom<char> for Digit {
+        type Error = String;
+
+        fn try_from(value: char) -> Result<Self, Self::Error> {
+            value
+                .to_digit(10)
+                .map(Digit)
+                .ok_or_else(|| format!("Not a digit: {:?}", value))
+        }
+    }
+
+    #[test]
+    fn test_indexing() {
+        let mut grid = digits();
+        assert_eq!((grid.rows(), grid.cols()), (3, 4));
+        assert_eq!(grid[(1, 2)], 7);
+        assert_eq!(grid.get((2, 3)), Some(&2));
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((0, 4)), None);
+        grid[(2, 1)] = 10;
+        *grid.get_mut((0, 0)).unwrap() += 10;
+        assert_eq!(grid.row(2), &[9, 10, 1, 2]);
+        assert_eq!(grid.row(0)[0], 11);
+        assert_eq!(grid.position(|cell| *cell == 8), Some((1, 3)));
+        assert_eq!(grid.positions().nth(5), Some(((1, 1), &6)));
+        assert!(Grid::from_rows(vec![vec![1], vec![2, 3]]).is_none());
+        assert_eq!(Grid::new(2, 3, 'x').iter().count(), 6);
+    }
+
+    #[test]
+    #[should_panic(expected = "Position (0, 4) out of a 3x4 grid")]
+    fn test_index_out_of_bounds() {
+        let _ = digits()[(0, 4)];
+    }
+
+    #[test]
+    fn test_lines() {
+        let mut grid = digits();
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), vec![2, 6, 0]);
+        assert_eq!(
+            grid.column(3).rev().copied().collect::<Vec<_>>(),
+            vec![2, 8, 4]
+        );
+        assert_eq!(grid.column(0).len(), 3);
+        assert_eq!(
+            grid.diagonal((0, 0)).copied().collect::<Vec<_>>(),
+            vec![1, 6, 1]
+        );
+        assert_eq!(
+            grid.diagonal((0, 2)).copied().collect::<Vec<_>>(),
+            vec![3, 8]
+        );
+        assert_eq!(grid.diagonal((2, 3)).copied().collect::<Vec<_>>(), vec![2]);
+        assert_eq!(grid.diagonal((3, 0)).count(), 0);
+        assert_eq!(
+            grid.anti_diagonal((0, 3)).copied().collect::<Vec<_>>(),
+            vec![4, 7, 0]
+        );
+        assert_eq!(
+            grid.anti_diagonal((1, 0)).copied().collect::<Vec<_>>(),
+            vec![5]
+        );
+        assert_eq!(
+            grid.iter_rows().rev().map(|row| row[0]).collect::<Vec<_>>(),
+            vec![9, 5, 1]
+        );
+
+        for cell in grid.column_mut(0) {
+            *cell *= 10;
+        }
+        for cell in grid.diagonal_mut((0, 1)) {
+            *cell += 100;
+        }
+        for cell in grid.anti_diagonal_mut((0, 3)).rev().take(1) {
+            *cell = 0;
+        }
+        grid.row_mut(2)[3] = 5;
+        for row in grid.iter_rows_mut() {
+            row[3] += 1;
+        }
+        assert_eq!(
+            grid,
+            Grid::from_rows(vec![
+                vec![10, 102, 3, 5],
+                vec![50, 6, 107, 9],
+                vec![90, 0, 1, 6],
+            ])
+            .unwrap()
+        );
+
+        // single row and column grids have no gaps between diagonal cells
+        let column = Grid::from_rows(vec![vec![1], vec![2]]).unwrap();
+        assert_eq!(column.anti_diagonal((0, 0)).count(), 1);
+        assert_eq!(column.diagonal((0, 0)).count(), 1);
+        let row = Grid::from_rows(vec![vec![1, 2]]).unwrap();
+        assert_eq!(row.column(1).copied().collect::<Vec<_>>(), vec![2]);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = digits();
+        assert_eq!(
+            grid.neighbours4((1, 1)).collect::<Vec<_>>(),
+            vec![(0, 1), (1, 0), (1, 2), (2, 1)]
+        );
+        assert_eq!(
+            grid.neighbours4((0, 3)).collect::<Vec<_>>(),
+            vec![(0, 2), (1, 3)]
+        );
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+        assert_eq!(
+            grid.neighbours8((2, 0)).collect::<Vec<_>>(),
+            vec![(1, 0), (1, 1), (2, 1)]
+        );
+        assert_eq!(grid.neighbours8((5, 5)).count(), 0);
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = Grid::<Digit>::parse("123\n456\n\n").unwrap();
+        assert_eq!(
+            grid.map(|digit| digit.0),
+            Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap()
+        );
+        let error = |input| {
+            Grid::<Digit>::parse(input)
+                .unwrap_err()
+                .downcast::<ParseError>()
+                .unwrap()
+        };
+        let e = error("123\n4x6\n");
+        assert_eq!((e.line, e.column, e.day), (2, 2, None));
+        assert_eq!(e.snippet, "4x6");
+        assert_eq!(e.message, "Not a digit: 'x'");
+        let e = error("123\n\n45\n");
+        assert_eq!((e.line, e.column), (3, 3));
+        assert_eq!(e.snippet, "45");
+        assert_eq!(e.message, "Row has 2 cells, expected 3 like the first row");
+        let e = error("12\r\n1234\n");
+        assert_eq!((e.line, e.column), (2, 3));
+        assert_eq!(e.snippet, "1234");
+        let empty = Grid::<Digit>::parse("").unwrap();
+        assert_eq!((empty.rows(), empty.cols()), (0, 0));
+        assert_eq!(empty.iter_rows().count(), 0);
+        assert_eq!(empty.anti_diagonal((0, 0)).count(), 0);
+    }
+}
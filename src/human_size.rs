@@ -0,0 +1,39 @@
+/// Size in bytes as `du -h` shows it: rounded up, with one decimal below 10 of a unit.
+pub fn format(size: usize) -> String {
+    const UNITS: [&str; 6] = ["K", "M", "G", "T", "P", "E"];
+
+    if size < 1024 {
+        return size.to_string();
+    }
+    let mut value = size as f64;
+    for unit in UNITS {
+        value /= 1024.0;
+        let rounded = if value < 10.0 {
+            (value * 10.0).ceil() / 10.0
+        } else {
+            value.ceil()
+        };
+        if rounded < 10.0 {
+            return format!("{:.1}{}", rounded, unit);
+        } else if rounded < 1024.0 {
+            return format!("{}{}", rounded, unit);
+        }
+    }
+    format!("{}{}", value.ceil(), UNITS[UNITS.len() - 1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format() {
+        assert_eq!(format(0), "0");
+        assert_eq!(format(1023), "1023");
+        assert_eq!(format(1024), "1.0K");
+        assert_eq!(format(1025), "1.1K");
+        assert_eq!(format(10 * 1024 - 1), "10K");
+        assert_eq!(format(1024 * 1024 - 1), "1.0M");
+        assert_eq!(format(3 << 30), "3.0G");
+    }
+}
@@ -0,0 +1,82 @@
+use std::{
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+
+/// Where the puzzle input is read from.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum Source {
+    /// `inputs/dayNN.txt` relative to the working directory.
+    #[default]
+    Default,
+    Path(PathBuf),
+    Stdin,
+}
+
+impl From<&str> for Source {
+    fn from(arg: &str) -> Self {
+        if arg == "-" {
+            Self::Stdin
+        } else {
+            Self::Path(arg.into())
+        }
+    }
+}
+
+impl Source {
+    pub fn read(&self, day: usize) -> anyhow::Result<String> {
+        match self {
+            Source::Default => read_file(&default_path(day)),
+            Source::Path(path) => read_file(path),
+            Source::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .context("Can't read input from stdin")?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+pub fn default_path(day: usize) -> PathBuf {
+    Path::new("inputs").join(format!("day{:02}.txt", day))
+}
+
+fn read_file(path: &Path) -> anyhow::Result<String> {
+    fs::read_to_string(path).with_context(|| {
+        format!(
+            "Can't read input file {} (pass `--input <PATH>` or `--input -` for stdin)",
+            path.display()
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_from_arg() {
+        assert_eq!(Source::from("-"), Source::Stdin);
+        assert_eq!(
+            Source::from("other/day07.txt"),
+            Source::Path("other/day07.txt".into())
+        );
+    }
+
+    #[test]
+    fn test_default_path() {
+        assert_eq!(default_path(7), Path::new("inputs/day07.txt"));
+        assert_eq!(default_path(13), Path::new("inputs/day13.txt"));
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let error = Source::from("inputs/missing.txt").read(1).unwrap_err();
+        assert!(format!("{}", error).contains("inputs/missing.txt"));
+    }
+}
@@ -0,0 +1,19 @@
+//! Advent of Code 2022 solutions.
+//!
+//! Every puzzle lives in its own module under [`days`] (e.g. [`days::day07::FS`],
+//! [`days::day13::Packet`]) together with its parser and `Problem` impl, so the
+//! domain types can be reused outside of the binaries.
+
+pub mod answers;
+pub mod bench;
+pub mod bits;
+pub mod cli;
+pub mod days;
+pub mod glob;
+pub mod grid;
+pub mod human_size;
+pub mod input;
+pub mod parse_error;
+pub mod runner;
+pub mod samples;
+pub mod template;
@@ -0,0 +1,303 @@
+use std::{
+    fmt::{self, Display},
+    marker::PhantomData,
+    ops::Range,
+};
+
+use aoc_helpers::scaffold::Parse;
+
+/// Error of a custom `Parse` impl pointing at the offending place in the input.
+///
+/// `line` and `column` are 1-based and relative to the text given to `Parse::parse`. Parsing
+/// through [`Lines`], [`Blocks`] or [`Sections`] makes them relative to the whole input, see
+/// [`parse_slice`]. Errors are equal if all their public fields are.
+#[derive(Clone, Debug)]
+pub struct ParseError {
+    /// `None` for parsers shared by several days, see [`ParseError::at`].
+    pub day: Option<usize>,
+    pub line: usize,
+    pub column: usize,
+    /// The whole line containing the error.
+    pub snippet: String,
+    pub message: String,
+    /// Byte offset of the error in the parsed text, only used by [`ParseError::shift`].
+    offset: usize,
+}
+
+fn locate(text: &str, mut offset: usize) -> (usize, usize, String) {
+    offset = offset.min(text.len());
+    while !text.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    let line_start = text[..offset].rfind('\n').map_or(0, |idx| idx + 1);
+    let line_end = text[offset..]
+        .find('\n')
+        .map_or(text.len(), |idx| offset + idx);
+    (
+        text[..line_start].matches('\n').count() + 1,
+        text[line_start..offset].chars().count() + 1,
+        text[line_start..line_end].trim_end_matches('\r').to_owned(),
+    )
+}
+
+impl ParseError {
+    /// Creates an error of `day` at byte `offset` of `text`.
+    pub fn new(day: usize, text: &str, offset: usize, message: impl Into<String>) -> Self {
+        Self {
+            day: Some(day),
+            ..Self::at(text, offset, message)
+        }
+    }
+
+    /// Creates an error at byte `offset` of `text` that doesn't belong to a particular day.
+    pub fn at(text: &str, offset: usize, message: impl Into<String>) -> Self {
+        let (line, column, snippet) = locate(text, offset);
+        Self {
+            day: None,
+            line,
+            column,
+            snippet,
+            message: message.into(),
+            offset: offset.min(text.len()),
+        }
+    }
+
+    /// Makes the position relative to `text` for an error in the slice of `text` starting at
+    /// byte `start`.
+    pub fn shift(&mut self, text: &str, start: usize) {
+        self.offset = (start + self.offset).min(text.len());
+        (self.line, self.column, self.snippet) = locate(text, self.offset);
+    }
+}
+
+impl PartialEq for ParseError {
+    fn eq(&self, other: &Self) -> bool {
+        (
+            self.day,
+            self.line,
+            self.column,
+            &self.snippet,
+            &self.message,
+        ) == (
+            other.day,
+            other.line,
+            other.column,
+            &other.snippet,
+            &other.message,
+        )
+    }
+}
+
+impl Eq for ParseError {}
+
+/// Parses `text[range]` with `P`, shifting a [`ParseError`] it fails with to its place in `text`.
+pub fn parse_slice<P: Parse>(text: &str, range: Range<usize>) -> anyhow::Result<P::Parsed> {
+    let start = range.start;
+    P::parse(&text[range]).map_err(|mut error| {
+        if let Some(parse_error) = error.downcast_mut::<ParseError>() {
+            parse_error.shift(text, start);
+        }
+        error
+    })
+}
+
+/// Non-blank lines, each parsed with `T`, like `VecFromLines`.
+pub struct Lines<T>(PhantomData<T>);
+
+impl<T: Parse> Parse for Lines<T> {
+    type Parsed = Vec<T::Parsed>;
+
+    fn parse(raw_input: &str) -> anyhow::Result<Self::Parsed> {
+        lines_with_offsets(raw_input)
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(start, line)| parse_slice::<T>(raw_input, start..start + line.len()))
+            .collect()
+    }
+}
+
+/// Non-blank blocks separated by blank lines, each parsed with `T`, like `VecFromMultiLines`.
+pub struct Blocks<T>(PhantomData<T>);
+
+impl<T: Parse> Parse for Blocks<T> {
+    type Parsed = Vec<T::Parsed>;
+
+    fn parse(raw_input: &str) -> anyhow::Result<Self::Parsed> {
+        let mut start = 0;
+        raw_input
+            .split("\n\n")
+            .map(|block| {
+                let range = start..start + block.len();
+                start = range.end + "\n\n".len();
+                range
+            })
+            .filter(|range| !raw_input[range.clone()].trim().is_empty())
+            .map(|range| parse_slice::<T>(raw_input, range))
+            .collect()
+    }
+}
+
+/// Two parts separated by the first blank line, parsed with `A` and `B`, like `TwoSections`.
+pub struct Sections<A, B>(PhantomData<(A, B)>);
+
+impl<A: Parse, B: Parse> Parse for Sections<A, B> {
+    type Parsed = (A::Parsed, B::Parsed);
+
+    fn parse(raw_input: &str) -> anyhow::Result<Self::Parsed> {
+        let (first, _) = raw_input
+            .split_once("\n\n")
+            .ok_or_else(|| anyhow::anyhow!("Expected two sections"))?;
+        Ok((
+            parse_slice::<A>(raw_input, 0..first.len())?,
+            parse_slice::<B>(raw_input, first.len() + "\n\n".len()..raw_input.len())?,
+        ))
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let gutter = " ".repeat(self.line.to_string().len());
+        match self.day {
+            Some(day) => write!(f, "Day {}, line", day)?,
+            None => f.write_str("Line")?,
+        }
+        writeln!(
+            f,
+            " {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", self.line, self.snippet)?;
+        write!(f, "{} | {}^", gutter, " ".repeat(self.column - 1))
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Lines of `text` together with the byte offset at which each of them starts.
+pub fn lines_with_offsets(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.split_inclusive('\n').scan(0, |offset, line| {
+        let start = *offset;
+        *offset += line.len();
+        Some((start, line.trim_end_matches('\n').trim_end_matches('\r')))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position() {
+        let error = ParseError::new(13, "[1,2]\n[1,x]\n", 9, "expected a number");
+        assert_eq!(error.line, 2);
+        assert_eq!(error.column, 4);
+        assert_eq!(error.snippet, "[1,x]");
+    }
+
+    #[test]
+    fn test_display() {
+        let error = ParseError::new(13, "[1,2]\n[1,x]\n", 9, "expected a number");
+        assert_eq!(
+            error.to_string(),
+            concat!(
+                "Day 13, line 2, column 4: expected a number\n",
+                "  |\n",
+                "2 | [1,x]\n",
+                "  |    ^",
+            )
+        );
+        assert_eq!(
+            ParseError::at("ab\ncd", 4, "expected a digit").to_string(),
+            concat!(
+                "Line 2, column 2: expected a digit\n",
+                "  |\n",
+                "2 | cd\n",
+                "  |  ^",
+            )
+        );
+    }
+
+    #[test]
+    fn test_shift() {
+        let input = "[1]\n[2]\n\n[3]\n[4,x]\n";
+        let mut error = ParseError::new(13, &input[13..18], 3, "expected a number");
+        assert_eq!((error.line, error.column), (1, 4));
+        error.shift(input, 13);
+        assert_eq!((error.line, error.column), (5, 4));
+        assert_eq!(error.snippet, "[4,x]");
+
+        // an error at the end of a slice ending the input stays at the end
+        let mut error = ParseError::new(13, &input[13..18], 5, "expected `]`");
+        error.shift(input, 13);
+        assert_eq!((error.line, error.column), (5, 6));
+    }
+
+    #[test]
+    fn test_eq() {
+        let first = "[1,x]".to_owned();
+        let second = format!("{}\n", first);
+        assert_eq!(
+            ParseError::new(13, &first, 3, "expected a number"),
+            ParseError::new(13, &second, 3, "expected a number")
+        );
+        assert_ne!(
+            ParseError::new(13, &first, 3, "expected a number"),
+            ParseError::at(&first, 3, "expected a number")
+        );
+    }
+
+    /// Fails on the first `x`.
+    struct NoX;
+
+    impl Parse for NoX {
+        type Parsed = String;
+
+        fn parse(raw_input: &str) -> anyhow::Result<Self::Parsed> {
+            match raw_input.find('x') {
+                Some(offset) => Err(ParseError::at(raw_input, offset, "x").into()),
+                None => Ok(raw_input.to_owned()),
+            }
+        }
+    }
+
+    fn position<P: Parse>(input: &str) -> (usize, usize) {
+        let error = P::parse(input)
+            .err()
+            .unwrap()
+            .downcast::<ParseError>()
+            .unwrap();
+        (error.line, error.column)
+    }
+
+    #[test]
+    fn test_wrappers() {
+        assert_eq!(
+            Lines::<NoX>::parse("a\n\r\nb\n").unwrap(),
+            vec!["a".to_owned(), "b".to_owned()]
+        );
+        assert_eq!(position::<Lines<NoX>>("a\n\nbcx\n"), (3, 3));
+        assert_eq!(position::<Lines<NoX>>("a\r\nbx\r\n"), (2, 2));
+
+        let blocks = Blocks::<Lines<NoX>>::parse("a\nb\n\nc\n").unwrap();
+        assert_eq!(blocks, vec![vec!["a", "b"], vec!["c"]]);
+        assert_eq!(
+            position::<Blocks<Lines<NoX>>>("a\nb\n\nc\n\n\nd\n ex"),
+            (8, 3)
+        );
+        assert_eq!(position::<Blocks<NoX>>("a\n\n\n\nb\ncx"), (6, 2));
+
+        let (first, second) = Sections::<NoX, Lines<NoX>>::parse("a\nb\n\nc\nd").unwrap();
+        assert_eq!((first.as_str(), second.len()), ("a\nb", 2));
+        assert_eq!(position::<Sections<NoX, Lines<NoX>>>("a\n\nb\ncx"), (4, 2));
+        assert_eq!(position::<Sections<NoX, NoX>>("ax\n\nb"), (1, 2));
+        assert!(Sections::<NoX, NoX>::parse("a\nb").is_err());
+    }
+
+    #[test]
+    fn test_lines_with_offsets() {
+        assert_eq!(
+            lines_with_offsets("ab\r\n\ncd").collect::<Vec<_>>(),
+            vec![(0, "ab"), (4, ""), (5, "cd")]
+        );
+    }
+}
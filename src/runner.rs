@@ -0,0 +1,389 @@
+use std::{
+    fmt::{self, Display},
+    ops::RangeInclusive,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context;
+use aoc_helpers::scaffold::{Parse, Problem};
+use serde::Serialize;
+
+use crate::{bench, days};
+
+/// Kind of value a part is answered with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AnswerType {
+    Integer,
+    String,
+}
+
+/// Types that `Problem::Part1` and `Problem::Part2` can be for a day to be registered.
+pub trait Answer: Display {
+    const TYPE: AnswerType;
+}
+
+macro_rules! integer_answers {
+    ($($t:ty),*) => {
+        $(
+            impl Answer for $t {
+                const TYPE: AnswerType = AnswerType::Integer;
+            }
+        )*
+    };
+}
+
+integer_answers!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl Answer for String {
+    const TYPE: AnswerType = AnswerType::String;
+}
+
+/// Answer to a single part, formatted for display, and how long solving it took.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PartAnswer {
+    pub answer: String,
+    pub answer_type: AnswerType,
+    pub duration: Duration,
+}
+
+impl PartAnswer {
+    fn solve<T: Answer>(solver: impl FnOnce() -> T) -> Self {
+        let start = Instant::now();
+        let answer = solver();
+        Self {
+            duration: start.elapsed(),
+            answer: answer.to_string(),
+            answer_type: T::TYPE,
+        }
+    }
+}
+
+/// Answers to both parts of a single day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Answers {
+    pub part1: PartAnswer,
+    pub part2: PartAnswer,
+}
+
+impl Answers {
+    pub fn parts(&self) -> [&PartAnswer; 2] {
+        [&self.part1, &self.part2]
+    }
+}
+
+/// Type-erased entry in the registry of days, see [`days::DAYS`].
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub number: usize,
+    solver: fn(&str) -> anyhow::Result<Answers>,
+    bencher: fn(&str, usize) -> anyhow::Result<bench::Timings>,
+}
+
+impl Day {
+    pub const fn new<P>(number: usize) -> Self
+    where
+        P: Problem,
+        P::Part1: Answer,
+        P::Part2: Answer,
+    {
+        Self {
+            number,
+            solver: solve::<P>,
+            bencher: bench::measure::<P>,
+        }
+    }
+
+    pub fn solve(&self, raw_input: &str) -> anyhow::Result<Answers> {
+        (self.solver)(raw_input).with_context(|| format!("Day {} failed", self.number))
+    }
+
+    pub fn bench(&self, raw_input: &str, repetitions: usize) -> anyhow::Result<bench::Timings> {
+        (self.bencher)(raw_input, repetitions)
+            .with_context(|| format!("Day {} failed", self.number))
+    }
+}
+
+fn solve<P>(raw_input: &str) -> anyhow::Result<Answers>
+where
+    P: Problem,
+    P::Part1: Answer,
+    P::Part2: Answer,
+{
+    let input = P::Input::parse(raw_input)?;
+    Ok(Answers {
+        part1: PartAnswer::solve(|| P::solve_part1(&input)),
+        part2: PartAnswer::solve(|| P::solve_part2(&input)),
+    })
+}
+
+/// Days picked on the command line: `all`, a single day (`7`) or an inclusive range (`3..9`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Selection {
+    All,
+    Range(RangeInclusive<usize>),
+}
+
+impl FromStr for Selection {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_day = |day: &str| {
+            day.parse::<usize>()
+                .map_err(|e| anyhow::anyhow!("Invalid day {:?}: {}", day, e))
+        };
+        if s == "all" {
+            Ok(Self::All)
+        } else if let Some((start, end)) = s.split_once("..") {
+            let end = end.strip_prefix('=').unwrap_or(end);
+            Ok(Self::Range(parse_day(start)?..=parse_day(end)?))
+        } else {
+            let day = parse_day(s)?;
+            Ok(Self::Range(day..=day))
+        }
+    }
+}
+
+impl Display for Selection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Selection::All => f.write_str("all"),
+            Selection::Range(range) if range.start() == range.end() => range.start().fmt(f),
+            Selection::Range(range) => write!(f, "{}..{}", range.start(), range.end()),
+        }
+    }
+}
+
+impl Selection {
+    pub fn days(&self) -> anyhow::Result<Vec<&'static Day>> {
+        let selected: Vec<&Day> = days::DAYS
+            .iter()
+            .filter(|day| match self {
+                Selection::All => true,
+                Selection::Range(range) => range.contains(&day.number),
+            })
+            .collect();
+        if selected.is_empty() {
+            Err(anyhow::anyhow!("No solved days in {}", self))
+        } else {
+            Ok(selected)
+        }
+    }
+}
+
+/// Renders answers as a table with one row per day.
+///
+/// Multi-line answers (like Day 10 part 2) continue on the following rows.
+pub fn render_table(results: &[(usize, Answers)]) -> String {
+    const HEADERS: [&str; 3] = ["Day", "Part 1", "Part 2"];
+
+    let rows: Vec<[Vec<String>; 3]> = results
+        .iter()
+        .map(|(day, answers)| {
+            [
+                vec![day.to_string()],
+                answer_lines(&answers.part1.answer),
+                answer_lines(&answers.part2.answer),
+            ]
+        })
+        .collect();
+    let mut widths = HEADERS.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = cell.iter().map(String::len).fold(*width, usize::max);
+        }
+    }
+
+    let mut table = String::new();
+    let mut push_line = |cells: [&str; 3]| {
+        let line = format!(
+            "{:>w0$} | {:<w1$} | {:<w2$}",
+            cells[0],
+            cells[1],
+            cells[2],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+        );
+        table.push_str(line.trim_end());
+        table.push('\n');
+    };
+    push_line(HEADERS);
+    push_line(widths.map(|w| "-".repeat(w)).each_ref().map(String::as_str));
+    for row in &rows {
+        let height = row.iter().map(Vec::len).max().unwrap_or_default();
+        for line in 0..height {
+            push_line(
+                row.each_ref()
+                    .map(|cell| cell.get(line).map_or("", String::as_str)),
+            );
+        }
+    }
+    table
+}
+
+fn answer_lines(answer: &str) -> Vec<String> {
+    answer
+        .trim_matches('\n')
+        .lines()
+        .map(str::to_owned)
+        .collect()
+}
+
+/// How `aoc run` prints the answers.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Table,
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(Self::Table),
+            "json" => Ok(Self::Json),
+            _ => Err(anyhow::anyhow!("Unknown output format: {:?}", s)),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonPart {
+    day: usize,
+    part: usize,
+    answer: serde_json::Value,
+    answer_type: AnswerType,
+    duration_ns: u64,
+}
+
+impl JsonPart {
+    fn new(day: usize, part: usize, answer: &PartAnswer) -> Self {
+        let text = || serde_json::Value::String(answer.answer.clone());
+        Self {
+            day,
+            part,
+            answer: match answer.answer_type {
+                AnswerType::Integer => answer
+                    .answer
+                    .parse::<serde_json::Number>()
+                    .map_or_else(|_| text(), serde_json::Value::Number),
+                AnswerType::String => text(),
+            },
+            answer_type: answer.answer_type,
+            duration_ns: u64::try_from(answer.duration.as_nanos()).unwrap_or(u64::MAX),
+        }
+    }
+}
+
+/// Renders answers as a JSON array with one object per solved part.
+pub fn render_json(results: &[(usize, Answers)]) -> String {
+    let parts: Vec<JsonPart> = results
+        .iter()
+        .flat_map(|(day, answers)| {
+            answers
+                .parts()
+                .into_iter()
+                .enumerate()
+                .map(|(idx, answer)| JsonPart::new(*day, idx + 1, answer))
+        })
+        .collect();
+    let mut json = serde_json::to_string_pretty(&parts).expect("Answers should serialize");
+    json.push('\n');
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selection_parsing() {
+        assert_eq!("all".parse::<Selection>().unwrap(), Selection::All);
+        assert_eq!("7".parse::<Selection>().unwrap(), Selection::Range(7..=7));
+        assert_eq!(
+            "3..9".parse::<Selection>().unwrap(),
+            Selection::Range(3..=9)
+        );
+        assert_eq!(
+            "3..=9".parse::<Selection>().unwrap(),
+            Selection::Range(3..=9)
+        );
+        assert!("x".parse::<Selection>().is_err());
+        assert!("3..".parse::<Selection>().is_err());
+    }
+
+    #[test]
+    fn test_selection_days() {
+        let numbers = |selection: Selection| {
+            selection
+                .days()
+                .unwrap()
+                .into_iter()
+                .map(|day| day.number)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(numbers(Selection::Range(3..=5)), vec![3, 4, 5]);
+        assert_eq!(numbers(Selection::All).len(), days::DAYS.len());
+        assert!(Selection::Range(100..=100).days().is_err());
+    }
+
+    fn part(answer: &str, answer_type: AnswerType) -> PartAnswer {
+        PartAnswer {
+            answer: answer.to_owned(),
+            answer_type,
+            duration: Duration::from_nanos(1500),
+        }
+    }
+
+    fn sample_results() -> [(usize, Answers); 2] {
+        [
+            (
+                1,
+                Answers {
+                    part1: part("24000", AnswerType::Integer),
+                    part2: part("45000", AnswerType::Integer),
+                },
+            ),
+            (
+                10,
+                Answers {
+                    part1: part("13140", AnswerType::Integer),
+                    part2: part("\n##..\n###.\n", AnswerType::String),
+                },
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_render_table() {
+        assert_eq!(
+            render_table(&sample_results()),
+            concat!(
+                "Day | Part 1 | Part 2\n",
+                "--- | ------ | ------\n",
+                "  1 | 24000  | 45000\n",
+                " 10 | 13140  | ##..\n",
+                "    |        | ###.\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_render_json() {
+        let json: serde_json::Value =
+            serde_json::from_str(&render_json(&sample_results())).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([
+                {"day": 1, "part": 1, "answer": 24000, "answer_type": "integer", "duration_ns": 1500},
+                {"day": 1, "part": 2, "answer": 45000, "answer_type": "integer", "duration_ns": 1500},
+                {"day": 10, "part": 1, "answer": 13140, "answer_type": "integer", "duration_ns": 1500},
+                {"day": 10, "part": 2, "answer": "\n##..\n###.\n", "answer_type": "string", "duration_ns": 1500},
+            ])
+        );
+    }
+}
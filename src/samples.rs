@@ -0,0 +1,128 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+
+use crate::{
+    answers::{Expected, Status},
+    days,
+};
+
+pub const DEFAULT_DIR: &str = "inputs/samples";
+
+/// Sample input `dayNN-<name>.txt` together with its sidecar `dayNN-<name>.toml`.
+///
+/// The sidecar holds the expected answers in the format of a single day in `answers.toml`
+/// (`part1 = 24000`), a part can be left out when the sample doesn't cover it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sample {
+    pub day: usize,
+    pub name: String,
+    pub path: PathBuf,
+    pub expected: Expected,
+}
+
+/// Splits a sample file stem like `day09-large` into the day and the name.
+fn parse_stem(stem: &str) -> Option<(usize, &str)> {
+    let (day, name) = stem.strip_prefix("day")?.split_once('-')?;
+    if day.len() != 2 || name.is_empty() {
+        return None;
+    }
+    Some((day.parse().ok()?, name))
+}
+
+impl Sample {
+    fn load(path: PathBuf) -> anyhow::Result<Self> {
+        let (day, name) = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(parse_stem)
+            .ok_or_else(|| {
+                anyhow::anyhow!("Sample {} isn't named dayNN-<name>.txt", path.display())
+            })?;
+        let sidecar = path.with_extension("toml");
+        let expected = fs::read_to_string(&sidecar)
+            .with_context(|| format!("Can't read expected answers from {}", sidecar.display()))?
+            .parse()
+            .with_context(|| format!("Can't parse expected answers from {}", sidecar.display()))?;
+        Ok(Self {
+            day,
+            name: name.to_owned(),
+            path,
+            expected,
+        })
+    }
+
+    /// Runs the sample through its day and compares the answers with the sidecar.
+    pub fn check(&self) -> anyhow::Result<[Status; 2]> {
+        let day =
+            days::find(self.day).ok_or_else(|| anyhow::anyhow!("Day {} isn't solved", self.day))?;
+        let raw_input = fs::read_to_string(&self.path)
+            .with_context(|| format!("Can't read sample {}", self.path.display()))?;
+        Ok(self.expected.clone().check(&day.solve(&raw_input)?))
+    }
+}
+
+/// Finds all samples in `dir`, ordered by day and name.
+///
+/// Every `.txt` file has to be a sample with a sidecar and every `.toml` file a sidecar of a sample.
+pub fn discover(dir: impl AsRef<Path>) -> anyhow::Result<Vec<Sample>> {
+    let dir = dir.as_ref();
+    let mut paths = fs::read_dir(dir)
+        .with_context(|| format!("Can't list samples in {}", dir.display()))?
+        .map(|entry| Ok(entry?.path()))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    paths.sort();
+    let mut samples = Vec::new();
+    for path in paths {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("txt") => samples.push(Sample::load(path)?),
+            Some("toml") if !path.with_extension("txt").exists() => {
+                return Err(anyhow::anyhow!(
+                    "Expected answers {} have no sample",
+                    path.display()
+                ))
+            }
+            _ => {}
+        }
+    }
+    samples.sort_by(|a, b| (a.day, &a.name).cmp(&(b.day, &b.name)));
+    Ok(samples)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_stem() {
+        assert_eq!(parse_stem("day09-large"), Some((9, "large")));
+        assert_eq!(parse_stem("day10-sample-2"), Some((10, "sample-2")));
+        assert_eq!(parse_stem("day9-large"), None);
+        assert_eq!(parse_stem("day09"), None);
+        assert_eq!(parse_stem("day09-"), None);
+        assert_eq!(parse_stem("sample"), None);
+    }
+
+    #[test]
+    fn test_samples() {
+        let samples = discover(DEFAULT_DIR).unwrap();
+        assert!(!samples.is_empty());
+        for sample in samples {
+            let statuses = sample
+                .check()
+                .unwrap_or_else(|e| panic!("{}: {:#}", sample.path.display(), e));
+            for (part, status) in statuses.iter().enumerate() {
+                assert!(
+                    !matches!(status, Status::Fail { .. }),
+                    "{} part {}: {:?}",
+                    sample.path.display(),
+                    part + 1,
+                    status
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,224 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+
+use crate::{input, samples};
+
+/// Library module of a new day with an unsolved `Problem` impl.
+///
+/// It has no test of its own, the generated sample is checked against its sidecar by
+/// `samples::tests::test_samples` once the answers are filled in.
+pub fn day_module(day: usize) -> String {
+    format!(
+        r#"use aoc_helpers::prelude::*;
+
+pub struct Day{day:02};
+
+impl Problem for Day{day:02} {{
+    type Input = VecFromLines<String>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn solve_part1(_input: &<Self::Input as aoc_helpers::scaffold::Parse>::Parsed) -> Self::Part1 {{
+        Default::default()
+    }}
+
+    fn solve_part2(_input: &<Self::Input as aoc_helpers::scaffold::Parse>::Parsed) -> Self::Part2 {{
+        Default::default()
+    }}
+}}
+"#
+    )
+}
+
+/// Binary running a single day, like the existing `src/bin/dayNN.rs`.
+pub fn day_binary(day: usize) -> String {
+    format!(
+        r#"use advent_of_code_2022::{{cli::day_input, days::day{day:02}::Day{day:02}}};
+use aoc_helpers::prelude::*;
+
+fn main() -> anyhow::Result<()> {{
+    solve::<Day{day:02}>(&day_input({day}, std::env::args().skip(1))?);
+    Ok(())
+}}
+"#
+    )
+}
+
+/// Sidecar of the generated sample, with the answers still to be filled in.
+pub const SAMPLE_ANSWERS: &str = "# part1 = \n# part2 = \n";
+
+/// Inserts `line` into the block of lines starting with `prefix`, keeping the block sorted
+/// by the day number that follows the prefix.
+fn insert_sorted(
+    lines: &mut Vec<String>,
+    prefix: &str,
+    day: usize,
+    line: String,
+) -> anyhow::Result<()> {
+    let number = |line: &str| -> Option<usize> {
+        let rest = line.strip_prefix(prefix)?;
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        rest[..digits].parse().ok()
+    };
+    let block: Vec<(usize, usize)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, line)| Some((idx, number(line)?)))
+        .collect();
+    if block.iter().any(|(_, number)| *number == day) {
+        return Err(anyhow::anyhow!("Day {} is already registered", day));
+    }
+    let idx = match block.iter().find(|(_, number)| *number > day) {
+        Some((idx, _)) => *idx,
+        None => match block.last() {
+            Some((idx, _)) => idx + 1,
+            None => {
+                return Err(anyhow::anyhow!(
+                    "Can't find lines starting with {:?}",
+                    prefix
+                ))
+            }
+        },
+    };
+    lines.insert(idx, line);
+    Ok(())
+}
+
+/// Adds the day's module declaration and its entry in `DAYS` to the contents of `days/mod.rs`.
+pub fn register(mod_rs: &str, day: usize) -> anyhow::Result<String> {
+    let mut lines: Vec<String> = mod_rs.lines().map(str::to_owned).collect();
+    insert_sorted(
+        &mut lines,
+        "pub mod day",
+        day,
+        format!("pub mod day{:02};", day),
+    )?;
+    insert_sorted(
+        &mut lines,
+        "    Day::new::<day",
+        day,
+        format!("    Day::new::<day{0:02}::Day{0:02}>({0}),", day),
+    )?;
+    let mut result = lines.join("\n");
+    result.push('\n');
+    Ok(result)
+}
+
+/// Generates the files of a new day under `root` (the repository root) and registers it.
+///
+/// Returns the paths of the created or changed files. Nothing is written if the day already exists.
+pub fn generate(root: &Path, day: usize) -> anyhow::Result<Vec<PathBuf>> {
+    if !(1..=25).contains(&day) {
+        return Err(anyhow::anyhow!("Invalid day {}, expected 1 to 25", day));
+    }
+    let mod_rs = root.join("src/days/mod.rs");
+    let module = root.join(format!("src/days/day{:02}.rs", day));
+    let binary = root.join(format!("src/bin/day{:02}.rs", day));
+    let input = root.join(input::default_path(day));
+    let sample = root
+        .join(samples::DEFAULT_DIR)
+        .join(format!("day{:02}-sample.txt", day));
+
+    let registered = register(
+        &fs::read_to_string(&mod_rs).with_context(|| {
+            format!(
+                "Can't read {}, `aoc new` has to be run from the repository root",
+                mod_rs.display()
+            )
+        })?,
+        day,
+    )?;
+    for path in [&module, &binary, &sample] {
+        if path.exists() {
+            return Err(anyhow::anyhow!("{} already exists", path.display()));
+        }
+    }
+
+    let mut written = Vec::new();
+    let mut write = |path: &Path, contents: &str| {
+        fs::write(path, contents).with_context(|| format!("Can't write {}", path.display()))?;
+        written.push(path.to_owned());
+        anyhow::Ok(())
+    };
+    write(&module, &day_module(day))?;
+    write(&binary, &day_binary(day))?;
+    write(&mod_rs, &registered)?;
+    if let Some(dir) = sample.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("Can't create {}", dir.display()))?;
+    }
+    write(&sample, "")?;
+    write(&sample.with_extension("toml"), SAMPLE_ANSWERS)?;
+    if !input.exists() {
+        write(&input, "")?;
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answers::Expected;
+
+    const MOD_RS: &str = concat!(
+        "use crate::runner::Day;\n",
+        "\n",
+        "pub mod day01;\n",
+        "pub mod day13;\n",
+        "\n",
+        "pub const DAYS: &[Day] = &[\n",
+        "    Day::new::<day01::Day01>(1),\n",
+        "    Day::new::<day13::Day13>(13),\n",
+        "];\n",
+    );
+
+    #[test]
+    fn test_register() {
+        assert_eq!(
+            register(MOD_RS, 2).unwrap(),
+            concat!(
+                "use crate::runner::Day;\n",
+                "\n",
+                "pub mod day01;\n",
+                "pub mod day02;\n",
+                "pub mod day13;\n",
+                "\n",
+                "pub const DAYS: &[Day] = &[\n",
+                "    Day::new::<day01::Day01>(1),\n",
+                "    Day::new::<day02::Day02>(2),\n",
+                "    Day::new::<day13::Day13>(13),\n",
+                "];\n",
+            )
+        );
+        let registered = register(MOD_RS, 14).unwrap();
+        assert!(registered.contains("pub mod day13;\npub mod day14;\n"));
+        assert!(registered.contains("(13),\n    Day::new::<day14::Day14>(14),\n];"));
+        assert!(register(MOD_RS, 13).is_err());
+    }
+
+    #[test]
+    fn test_real_registry() {
+        // the real registry has to stay in the format `register` understands
+        let mod_rs = include_str!("days/mod.rs");
+        let registered = register(mod_rs, 25).unwrap();
+        assert!(registered.contains("pub mod day25;"));
+        assert!(registered.contains("    Day::new::<day25::Day25>(25),"));
+    }
+
+    #[test]
+    fn test_templates() {
+        let module = day_module(14);
+        assert!(module.contains("pub struct Day14;"));
+        assert!(module.contains("impl Problem for Day14 {"));
+        assert!(!module.contains("assert"));
+        assert!(SAMPLE_ANSWERS.parse::<Expected>().is_ok());
+        let binary = day_binary(7);
+        assert!(binary.contains("days::day07::Day07"));
+        assert!(binary.contains("day_input(7, "));
+    }
+}